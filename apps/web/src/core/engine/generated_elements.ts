@@ -470,6 +470,9 @@ export interface Reaction {
   resultVictim: ElementType | null
   chance: number
   spawn: ElementType | null
+  minTemp?: number
+  maxTemp?: number
+  heatDelta?: number
 }
 
 export const REACTIONS: Reaction[] = [
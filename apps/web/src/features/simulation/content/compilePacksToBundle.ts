@@ -52,6 +52,11 @@ export type PackReactionFile = {
   resultVictim: string | null
   chance: number
   spawn: string | null
+  /** Temperature gate (mean of both cells, °C) */
+  minTemp?: number
+  maxTemp?: number
+  /** Added to both cells when the reaction fires */
+  heatDelta?: number
   comment?: string
 }
 
@@ -107,6 +112,9 @@ export type RuntimeBundle = {
     resultVictim?: string | null
     spawn?: string | null
     chance: number
+    minTemp?: number
+    maxTemp?: number
+    heatDelta?: number
     aggressorId: number
     victimId: number
     resultAggressorId: number | null
@@ -237,6 +245,13 @@ function parseReactionFile(raw: unknown, ctx: string): PackReactionFile {
   if (typeof rec.chance !== 'number' || !Number.isFinite(rec.chance) || rec.chance < 0 || rec.chance > 1) {
     throw new Error(`${ctx}: chance must be number in range 0..1`)
   }
+  for (const field of ['minTemp', 'maxTemp', 'heatDelta']) {
+    const v = rec[field]
+    if (v !== undefined && (typeof v !== 'number' || !Number.isFinite(v))) throw new Error(`${ctx}: ${field} must be number`)
+  }
+  if (typeof rec.minTemp === 'number' && typeof rec.maxTemp === 'number' && rec.minTemp > rec.maxTemp) {
+    throw new Error(`${ctx}: minTemp must not exceed maxTemp`)
+  }
 
   return rec as unknown as PackReactionFile
 }
@@ -498,7 +513,9 @@ export function compilePacksToBundleFromParsedPacks(args: { packs: PackInput[] }
     }
   }
 
-  const reactionsByPair = new Map<string, RuntimeBundle['reactions'][number]>()
+  // A pack may list several weighted outcomes for a pair (kept in order);
+  // a later pack replaces all earlier outcomes for the same pair
+  const reactionsByPair = new Map<string, { pack: string; outcomes: RuntimeBundle['reactions'] }>()
 
   for (const p of sortedPacks) {
     for (const { relPath, data } of p.reactionFiles) {
@@ -526,6 +543,9 @@ export function compilePacksToBundleFromParsedPacks(args: { packs: PackInput[] }
         resultVictim,
         spawn,
         chance: data.chance,
+        minTemp: data.minTemp,
+        maxTemp: data.maxTemp,
+        heatDelta: data.heatDelta,
         aggressorId: elementKeyToId[aggressor],
         victimId: elementKeyToId[victim],
         resultAggressorId: resultAggressor === null ? null : elementKeyToId[resultAggressor],
@@ -546,11 +566,17 @@ export function compilePacksToBundleFromParsedPacks(args: { packs: PackInput[] }
       }
 
       const pairKey = `${aggressor}::${victim}`
-      reactionsByPair.set(pairKey, out)
+      const existing = reactionsByPair.get(pairKey)
+      if (existing && existing.pack === p.manifest.id) {
+        existing.outcomes.push(out)
+      } else {
+        reactionsByPair.set(pairKey, { pack: p.manifest.id, outcomes: [out] })
+      }
     }
   }
 
-  const reactions = Array.from(reactionsByPair.values()).sort((a, b) => {
+  // Stable sort keeps each pair's outcomes in declaration order
+  const reactions = Array.from(reactionsByPair.values()).flatMap((pair) => pair.outcomes).sort((a, b) => {
     if (a.aggressorId !== b.aggressorId) return a.aggressorId - b.aggressorId
     return a.victimId - b.victimId
   })
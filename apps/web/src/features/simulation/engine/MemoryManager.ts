@@ -21,6 +21,7 @@ import { isStale as isStaleImpl } from './memoryManager/isStale'
 import { readWorldPointers } from './memoryManager/pointers'
 import { updateTracking } from './memoryManager/tracking'
 import { createWorldViews } from './memoryManager/views'
+import type { ElementIdArray } from './api/types'
 
 type WasmWorld = import('@particula/engine-wasm/particula_engine').World
 
@@ -29,7 +30,7 @@ export class MemoryManager {
   private engine: WasmWorld  // WASM World instance
   
   // Cached views (recreated on buffer detach)
-  private _types: Uint8Array | null = null
+  private _types: ElementIdArray | null = null
   private _colors: Uint32Array | null = null
   private _temperature: Float32Array | null = null
   
@@ -116,7 +117,7 @@ export class MemoryManager {
   /**
    * Get types view (element IDs) - always valid
    */
-  get types(): Uint8Array {
+  get types(): ElementIdArray {
     this.ensureValid()
     if (!this._types) {
       throw new Error('MemoryManager: types view unavailable')
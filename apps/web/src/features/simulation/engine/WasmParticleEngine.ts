@@ -10,7 +10,7 @@
  */
 
 import { CanvasRenderer } from './rendering/Renderer'
-import type { ElementType, RenderMode } from './api/types'
+import type { ElementIdArray, ElementType, RenderMode } from './api/types'
 import { debugWarn } from '@/platform/logging/log'
 
 export { isWasmAvailable, loadWasmEngine } from './wasm/loader'
@@ -44,7 +44,7 @@ export class WasmParticleEngine {
   private _height: number
   
   // TypedArray views into WASM memory (updated each frame)
-  private typesView: Uint8Array | null = null
+  private typesView: ElementIdArray | null = null
   private colorsView: Uint32Array | null = null
   private temperatureView: Float32Array | null = null
   
@@ -181,13 +181,13 @@ export class WasmParticleEngine {
     this.renderer.render(this.typesView, this.colorsView, this.temperatureView ?? undefined)
   }
   
-  /** Snapshot world types (Uint8Array copy) */
-  saveSnapshot(): Uint8Array | null {
+  /** Snapshot world types (copy, same id width as the engine) */
+  saveSnapshot(): ElementIdArray | null {
     return saveSnapshotImpl(this.typesView)
   }
   
   /** Load snapshot (types only) */
-  loadSnapshot(types: Uint8Array): void {
+  loadSnapshot(types: ElementIdArray): void {
     const nextWorld = loadSnapshotImpl({
       wasm: this.wasm,
       width: this._width,
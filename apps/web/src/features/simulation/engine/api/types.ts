@@ -53,8 +53,11 @@ export interface Particle {
   lifetime: number
 }
 
+/** One element id per cell; `Uint16Array` when the engine is built with `wide-element-ids` */
+export type ElementIdArray = Uint8Array | Uint16Array
+
 export interface IRenderer {
-  render(types: Uint8Array, colors: Uint32Array): void
+  render(types: ElementIdArray, colors: Uint32Array): void
   resize(width: number, height: number): void
 }
 
@@ -2,6 +2,7 @@ import type { ISimulationBackend, SimulationSettings } from '../api/ISimulationB
 import type { RenderMode } from '../api/types'
 
 import type { WasmParticleEngine } from '../WasmParticleEngine'
+import { createElementIdView } from '../wasm/views'
 import type { WorkerBridge } from '../worker/WorkerBridge'
 
 export function createWorkerBackend(bridge: WorkerBridge): ISimulationBackend {
@@ -36,8 +37,8 @@ export function createWasmBackend(engine: WasmParticleEngine): ISimulationBacken
       const snap = engine.saveSnapshot()
       if (!snap) return null
       // Ensure we always hand out an ArrayBuffer (not ArrayBufferLike) for transfer-safety.
-      return (new Uint8Array(snap)).buffer as ArrayBuffer
+      return snap.slice().buffer as ArrayBuffer
     },
-    loadSnapshot: (buffer: ArrayBuffer) => engine.loadSnapshot(new Uint8Array(buffer)),
+    loadSnapshot: (buffer: ArrayBuffer) => engine.loadSnapshot(createElementIdView(buffer)),
   }
 }
@@ -8,6 +8,7 @@
  * - Uint32Array.fill(): 50-100x faster clear
  * - No object access = no pointer chasing = cache friendly
  */
+import type { ElementIdArray } from '../api/types'
 import { createRenderBuffer, resizeRenderBuffer } from './canvas/buffer'
 import { drawBufferToScreen } from './canvas/drawBufferToScreen'
 import { renderNormalTyped as renderNormalTypedPixels } from './canvas/renderNormalTyped'
@@ -110,7 +111,7 @@ export class CanvasRenderer {
   }
 
   // NEW API: Accept TypedArrays directly
-  render(types: Uint8Array, colors: Uint32Array, temperatureData?: Float32Array): void {
+  render(types: ElementIdArray, colors: Uint32Array, temperatureData?: Float32Array): void {
     // 1. Render pixels to BUFFER
     if (this.mode === 'thermal' && temperatureData) {
       this.renderThermal(types, temperatureData)
@@ -192,7 +193,7 @@ export class CanvasRenderer {
    * WASM now returns ABGR format - direct copy with pixels32.set()
    * ~3-5x faster than byte-by-byte unpacking
    */
-  private renderNormalTyped(types: Uint8Array, colors: Uint32Array): void {
+  private renderNormalTyped(types: ElementIdArray, colors: Uint32Array): void {
     const pixels32 = this.pixels32
     
     // Fast path: Direct copy all colors (WASM provides ABGR format)
@@ -212,7 +213,7 @@ export class CanvasRenderer {
   /**
    * Render thermal vision - temperature to color gradient
    */
-  private renderThermal(types: Uint8Array, temps: Float32Array): void {
+  private renderThermal(types: ElementIdArray, temps: Float32Array): void {
     const pixels = this.pixels
 
     renderThermalPixels({
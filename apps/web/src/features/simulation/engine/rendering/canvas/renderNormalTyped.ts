@@ -1,8 +1,9 @@
+import type { ElementIdArray } from '../../api/types'
 import { EL_EMPTY } from '../../api/types'
 
 export function renderNormalTyped(args: {
   pixels32: Uint32Array
-  types: Uint8Array
+  types: ElementIdArray
   colors: Uint32Array
   width: number
   height: number
@@ -1,7 +1,9 @@
+import type { ElementIdArray } from '../../api/types'
+
 export function renderThermal(args: {
   pixels: Uint8ClampedArray
   temps: Float32Array
-  types?: Uint8Array
+  types?: ElementIdArray
   ambientTemp?: number
   width: number
   height: number
@@ -0,0 +1,20 @@
+import type { WasmModule } from './types'
+
+// Bump together with `abi_version()` in packages/engine/src/lib.rs
+const EXPECTED_ABI_VERSION = 2
+
+let elementIdBytes = 1
+
+/** Reject an engine build with a different memory layout and remember its element id width */
+export function checkEngineAbi(wasm: WasmModule): void {
+  const abiVersion = wasm.abi_version()
+  if (abiVersion !== EXPECTED_ABI_VERSION) {
+    throw new Error(`WASM engine ABI ${abiVersion} does not match expected ${EXPECTED_ABI_VERSION}`)
+  }
+  elementIdBytes = wasm.element_id_bytes()
+}
+
+/** Bytes per element id in the loaded engine: 1, or 2 in `wide-element-ids` builds */
+export function getElementIdBytes(): number {
+  return elementIdBytes
+}
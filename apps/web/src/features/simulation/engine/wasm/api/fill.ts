@@ -1,11 +1,11 @@
-import type { ElementType } from '../../api/types'
+import type { ElementIdArray, ElementType } from '../../api/types'
 import { ELEMENT_NAME_TO_ID } from '../../api/types'
 import type { WasmWorld } from '../types'
 import { floodFillInPlace } from '../fill'
 
 export function floodFill(args: {
   world: WasmWorld
-  typesView: Uint8Array | null
+  typesView: ElementIdArray | null
   width: number
   height: number
   cx: number
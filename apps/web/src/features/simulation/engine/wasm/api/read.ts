@@ -1,5 +1,7 @@
+import type { ElementIdArray } from '../../api/types'
+
 export function getElementAt(args: {
-  typesView: Uint8Array | null
+  typesView: ElementIdArray | null
   width: number
   height: number
   x: number
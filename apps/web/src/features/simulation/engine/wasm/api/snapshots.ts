@@ -1,16 +1,17 @@
+import type { ElementIdArray } from '../../api/types'
 import type { WasmModule, WasmWorld } from '../types'
 import { recreateWorldFromSnapshot } from '../snapshot'
 
-export function saveSnapshot(typesView: Uint8Array | null): Uint8Array | null {
+export function saveSnapshot(typesView: ElementIdArray | null): ElementIdArray | null {
   if (!typesView) return null
-  return new Uint8Array(typesView)
+  return typesView.slice()
 }
 
 export function loadSnapshot(args: {
   wasm: WasmModule
   width: number
   height: number
-  types: Uint8Array
+  types: ElementIdArray
   warn: (message: string) => void
 }): WasmWorld | null {
   const { wasm, width, height, types, warn } = args
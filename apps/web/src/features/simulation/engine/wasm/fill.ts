@@ -1,8 +1,10 @@
+import type { ElementIdArray } from '../api/types'
+
 type WasmWorld = import('@particula/engine-wasm/particula_engine').World
 
 export function floodFillInPlace(args: {
   world: WasmWorld
-  typesView: Uint8Array
+  typesView: ElementIdArray
   width: number
   height: number
   startX: number
@@ -1,5 +1,7 @@
 import { debugLog, logError } from '@/platform/logging/log'
 
+import { checkEngineAbi } from './abi'
+
 type WasmModule = typeof import('@particula/engine-wasm/particula_engine')
 type WasmInitOutput = import('@particula/engine-wasm/particula_engine').InitOutput
 
@@ -20,6 +22,8 @@ export async function loadWasmEngine(): Promise<WasmModule> {
       throw new Error('WASM memory not available')
     }
 
+    checkEngineAbi(wasm)
+
     wasmModule = wasm
     wasmModule.init()
 
@@ -1,3 +1,5 @@
+import type { ElementIdArray } from '../api/types'
+
 type WasmModule = typeof import('@particula/engine-wasm/particula_engine')
 type WasmWorld = import('@particula/engine-wasm/particula_engine').World
 
@@ -5,7 +7,7 @@ export function recreateWorldFromSnapshot(args: {
   wasm: WasmModule
   width: number
   height: number
-  types: Uint8Array
+  types: ElementIdArray
 }): WasmWorld {
   const { wasm, width, height, types } = args
 
@@ -1,11 +1,21 @@
+import type { ElementIdArray } from '../api/types'
+import { getElementIdBytes } from './abi'
+
 type WasmWorld = import('@particula/engine-wasm/particula_engine').World
 
 export type WorldMemoryViews = {
-  types: Uint8Array
+  types: ElementIdArray
   colors: Uint32Array
   temperature: Float32Array
 }
 
+/** View element ids with the width of the loaded engine build (`element_id_bytes()`) */
+export function createElementIdView(buffer: ArrayBufferLike, byteOffset = 0, length?: number): ElementIdArray {
+  return getElementIdBytes() === 2
+    ? new Uint16Array(buffer, byteOffset, length)
+    : new Uint8Array(buffer, byteOffset, length)
+}
+
 export function createWorldMemoryViews(world: WasmWorld, memory: WebAssembly.Memory): WorldMemoryViews {
   const typesPtr = world.types_ptr()
   const colorsPtr = world.colors_ptr()
@@ -13,7 +23,7 @@ export function createWorldMemoryViews(world: WasmWorld, memory: WebAssembly.Mem
   const size = world.types_len()
 
   return {
-    types: new Uint8Array(memory.buffer, typesPtr, size),
+    types: createElementIdView(memory.buffer, typesPtr, size),
     colors: new Uint32Array(memory.buffer, colorsPtr, size),
     temperature: new Float32Array(memory.buffer, tempPtr, size),
   }
@@ -1,3 +1,4 @@
+import type { ElementIdArray } from '../api/types'
 import type { WasmModule, WasmWorld } from './types'
 import { recreateWorldFromSnapshot } from './snapshot'
 
@@ -12,7 +13,7 @@ export function recreateWorld(args: {
   wasm: WasmModule
   width: number
   height: number
-  types: Uint8Array
+  types: ElementIdArray
 }): WasmWorld {
   return recreateWorldFromSnapshot(args)
 }
@@ -9,6 +9,7 @@ import { resetWorkerContext } from './context'
 import { postWorkerError } from './errors'
 import { applyCurrentSettingsToEngine, updateMemoryViews } from './memory'
 import { startRenderLoop } from './loop'
+import { checkEngineAbi } from '@/features/simulation/engine/wasm/abi'
 
 function postContentBundleStatus(args: {
   phase: 'init' | 'reload'
@@ -60,6 +61,7 @@ export async function initEngine(
 
     const wasm = await import('@particula/engine-wasm/particula_engine')
     const wasmExports: WasmInitOutput = await wasm.default()
+    checkEngineAbi(wasm)
 
     state.wasm.module = wasm
     state.wasm.memory = wasmExports.memory
@@ -1,4 +1,5 @@
-import type { ElementId } from '@/features/simulation/engine/api/types'
+import type { ElementId, ElementIdArray } from '@/features/simulation/engine/api/types'
+import { createElementIdView } from '@/features/simulation/engine/wasm/views'
 import { debugWarn, logError } from '@/platform/logging/log'
 
 import type { WorkerContext } from './context'
@@ -17,7 +18,7 @@ export function captureSnapshot(ctx: WorkerContext): ArrayBuffer | null {
       return null
     }
     const types = state.memory.manager.types
-    return new Uint8Array(types).buffer
+    return types.slice().buffer
   } catch (e) {
     logError('captureSnapshot failed:', e)
     return null
@@ -28,7 +29,7 @@ export function loadSnapshotBuffer(ctx: WorkerContext, buffer: ArrayBuffer): voi
   const state = ctx.state
   if (!state.wasm.engine || !state.wasm.module) return
 
-  const types = new Uint8Array(buffer)
+  const types = createElementIdView(buffer)
   const width = state.wasm.engine.width as number
   const height = state.wasm.engine.height as number
   const expected = width * height
@@ -62,15 +63,14 @@ export function readElementAt(ctx: WorkerContext, x: number, y: number): Element
   const height = state.wasm.engine.height as number
   if (x < 0 || y < 0 || x >= width || y >= height) return null
 
-  let types: Uint8Array
+  let types: ElementIdArray
   try {
     types = state.memory.manager.types
   } catch {
     return null
   }
   const idx = y * width + x
-  const elId = types[idx] ?? 0
-  return (Math.max(0, Math.min(255, Math.floor(elId))) as ElementId) ?? null
+  return (types[idx] ?? 0) as ElementId
 }
 
 export function floodFill(ctx: WorkerContext, startX: number, startY: number, targetElementId: number): void {
@@ -81,7 +81,7 @@ export function floodFill(ctx: WorkerContext, startX: number, startY: number, ta
   const height = state.wasm.engine.height as number
   if (startX < 0 || startY < 0 || startX >= width || startY >= height) return
 
-  let types: Uint8Array
+  let types: ElementIdArray
   try {
     types = state.memory.manager.types
   } catch (e) {
@@ -2,15 +2,17 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use super::content_report::{ContentError, ContentReport, ContentWarning};
+
 use crate::elements::{
     BehaviorKind, CategoryId, ElementFlags, ElementId, ElementProps, PhaseChange, Reaction,
-    REACTION_LUT_SIZE, BEHAVIOR_KIND_BY_ID, CAT_BIO, CAT_ENERGY, CAT_GAS, CAT_LIQUID, CAT_POWDER,
+    ReactionSystem, BEHAVIOR_KIND_BY_ID, CAT_BIO, CAT_ENERGY, CAT_GAS, CAT_LIQUID, CAT_POWDER,
     CAT_SOLID, CAT_UTILITY, ELEMENT_DATA, EL_ACID, EL_CLONE, EL_DIRT, EL_ELECTRICITY, EL_EMPTY,
     EL_FIRE, EL_GUNPOWDER, EL_ICE, EL_LAVA, EL_METAL, EL_OIL, EL_PLANT, EL_SAND, EL_SEED, EL_SMOKE,
     EL_SPARK, EL_STEAM, EL_STONE, EL_VOID, EL_WATER, EL_WOOD, FLAG_BIO, FLAG_COLD,
     FLAG_CONDUCTIVE, FLAG_CORROSIVE, FLAG_ENERGY, FLAG_FLAMMABLE, FLAG_GAS, FLAG_HOT,
     FLAG_IGNORE_GRAVITY, FLAG_LIQUID, FLAG_NONE, FLAG_POWDER, FLAG_RIGID, FLAG_SOLID,
-    FLAG_UTILITY, PHASE_CHANGES, REACTION_INIT_DATA,
+    FLAG_UTILITY, PHASE_CHANGES,
 };
 
 #[derive(Clone)]
@@ -18,23 +20,36 @@ pub struct ContentRegistry {
     elements: Vec<ElementProps>,
     behavior_kind_by_id: Vec<BehaviorKind>,
     phase_changes: Vec<PhaseChange>,
-    reaction_lut: Vec<Option<Reaction>>,
+    reactions: ReactionSystem,
     element_key_to_id: HashMap<String, ElementId>,
     element_manifest: Vec<ContentManifestElement>,
 }
 
 impl ContentRegistry {
     pub fn from_bundle_json(json: &str) -> Result<Self, String> {
-        let bundle: BundleRoot = serde_json::from_str(json).map_err(|e| e.to_string())?;
-        Self::from_bundle(bundle)
+        let (registry, report) = Self::from_bundle_json_with_report(json);
+        registry.ok_or_else(|| report.error_summary())
     }
 
-    pub fn from_generated() -> Self {
-        let mut reaction_lut = vec![None; REACTION_LUT_SIZE];
-        for (idx, reaction) in REACTION_INIT_DATA.iter() {
-            reaction_lut[*idx] = Some(*reaction);
+    /// Build a registry and report every error and warning found on the way
+    /// The registry is `None` when the report has errors.
+    pub fn from_bundle_json_with_report(json: &str) -> (Option<Self>, ContentReport) {
+        match serde_json::from_str::<BundleRoot>(json) {
+            Ok(bundle) => Self::from_bundle(bundle),
+            Err(e) => {
+                let mut report = ContentReport::default();
+                report.error(ContentError::Parse { message: e.to_string() });
+                (None, report)
+            }
         }
+    }
 
+    /// Check a bundle without loading it (ModStudio problem list)
+    pub fn validate_bundle_json(json: &str) -> ContentReport {
+        Self::from_bundle_json_with_report(json).1
+    }
+
+    pub fn from_generated() -> Self {
         let mut element_key_to_id = HashMap::new();
         element_key_to_id.insert("base:empty".to_string(), EL_EMPTY);
         element_key_to_id.insert("base:stone".to_string(), EL_STONE);
@@ -81,6 +96,7 @@ impl ContentRegistry {
                 color: props.color,
                 hidden: idx == (EL_EMPTY as usize),
                 ui: None,
+                tags: Vec::new(),
             });
         }
 
@@ -88,7 +104,7 @@ impl ContentRegistry {
             elements,
             behavior_kind_by_id: BEHAVIOR_KIND_BY_ID.to_vec(),
             phase_changes: PHASE_CHANGES.to_vec(),
-            reaction_lut,
+            reactions: ReactionSystem::new(),
             element_key_to_id,
             element_manifest,
         }
@@ -135,15 +151,36 @@ impl ContentRegistry {
         None
     }
 
-    pub fn reaction(&self, aggressor: ElementId, victim: ElementId) -> Option<&Reaction> {
-        let idx = ((aggressor as usize) << 8) | (victim as usize);
-        self.reaction_lut.get(idx)?.as_ref()
+    /// Candidate reactions for the pair; one roll picks at most one (`pick_reaction`)
+    pub fn reactions(&self, aggressor: ElementId, victim: ElementId) -> &[Reaction] {
+        self.reactions.get(aggressor, victim)
     }
 
     pub fn id_by_key(&self, key: &str) -> Option<ElementId> {
         self.element_key_to_id.get(key).copied()
     }
 
+    /// Canonical `pack:name` key of an element (from the manifest)
+    pub fn key_by_id(&self, id: ElementId) -> Option<&str> {
+        self.element_manifest.get(id as usize).map(|m| m.key.as_str())
+    }
+
+    /// Hidden elements are not offered in palettes (manifest `hidden`)
+    pub fn is_hidden(&self, id: ElementId) -> bool {
+        self.element_manifest.get(id as usize).is_some_and(|m| m.hidden)
+    }
+
+    /// All `key -> id` entries, sorted by id then key (stable for serialization)
+    pub fn element_key_table(&self) -> Vec<(&str, ElementId)> {
+        let mut table: Vec<(&str, ElementId)> = self
+            .element_key_to_id
+            .iter()
+            .map(|(k, v)| (k.as_str(), *v))
+            .collect();
+        table.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+        table
+    }
+
     pub fn manifest_json(&self) -> String {
         let out = ContentManifest {
             format_version: 1,
@@ -165,40 +202,89 @@ impl ContentRegistry {
         Some((a << 24) | (b << 16) | (g << 8) | r)
     }
 
-    fn from_bundle(bundle: BundleRoot) -> Result<Self, String> {
-        let mut max_id: u16 = 0;
-        for el in bundle.elements.iter() {
-            if el.id > max_id {
-                max_id = el.id;
+    /// Override one property of an element in place (live tuning, no reload)
+    pub fn set_element_property(&mut self, id: ElementId, property: ElementProperty) -> Result<(), String> {
+        let props = self
+            .elements
+            .get_mut(id as usize)
+            .ok_or_else(|| format!("element id out of range: {}", id))?;
+        match property {
+            ElementProperty::Density(v) => props.density = v,
+            ElementProperty::Dispersion(v) => props.dispersion = v,
+            ElementProperty::Friction(v) => props.friction = v,
+            ElementProperty::Bounce(v) => props.bounce = v,
+            ElementProperty::HeatConductivity(v) => props.heat_conductivity = v,
+            ElementProperty::Color(v) => {
+                props.color = v;
+                self.element_manifest[id as usize].color = v;
             }
         }
+        Ok(())
+    }
+
+    /// Build a registry from a parsed bundle, collecting every problem instead of
+    /// stopping at the first. The registry is `None` when the report has errors.
+    fn from_bundle(bundle: BundleRoot) -> (Option<Self>, ContentReport) {
+        let mut report = ContentReport::default();
 
-        if max_id > (u8::MAX as u16) {
-            return Err(format!("too many elements for u8 ids: max_id={}", max_id));
+        let mut len = 0usize;
+        for el in bundle.elements.iter() {
+            if u32::from(el.id) >= u32::from(Reaction::NO_CHANGE) {
+                report.error(ContentError::ElementIdOutOfRange { element: el.key.clone(), id: el.id });
+            } else {
+                len = len.max(el.id as usize + 1);
+            }
         }
 
-        let len = (max_id as usize) + 1;
         let mut props_by_id: Vec<Option<ElementProps>> = vec![None; len];
         let mut behavior_by_id: Vec<Option<BehaviorKind>> = vec![None; len];
         let mut phase_by_id: Vec<Option<PhaseChange>> = vec![None; len];
+        let mut tags_by_id: Vec<Vec<String>> = vec![Vec::new(); len];
         let mut manifest_by_id: Vec<Option<ContentManifestElement>> = vec![None; len];
+        // (element key, field, target id), checked once every element is known
+        let mut phase_targets: Vec<(String, &'static str, u16)> = Vec::new();
 
-        let mut element_key_to_id = HashMap::new();
+        // Bundle ids per key; narrowed to `ElementId` once the bundle is valid
+        let mut element_key_to_id: HashMap<String, u16> = HashMap::new();
 
         for el in bundle.elements.into_iter() {
+            if u32::from(el.id) >= u32::from(Reaction::NO_CHANGE) {
+                continue;
+            }
             let id = el.id as ElementId;
+            let idx = id as usize;
+            if let Some(existing) = &manifest_by_id[idx] {
+                report.error(ContentError::DuplicateElementId {
+                    element: el.key,
+                    id: el.id,
+                    existing: existing.key.clone(),
+                });
+                continue;
+            }
+            if element_key_to_id.contains_key(&el.key) {
+                report.error(ContentError::DuplicateElementKey { element: el.key });
+                continue;
+            }
 
-            let (category_id, category_flag) = category_from_str(&el.category)?;
+            // Keep going with a placeholder so later fields are still checked
+            let (category_id, category_flag) = category_from_str(&el.category).unwrap_or_else(|| {
+                report.error(ContentError::UnknownCategory {
+                    element: el.key.clone(),
+                    category: el.category.clone(),
+                });
+                (CAT_SOLID, FLAG_SOLID)
+            });
 
             let density = match el.density {
                 Some(v) => v,
                 None => match category_id {
                     CAT_UTILITY | CAT_ENERGY | CAT_BIO => 0.0,
                     _ => {
-                        return Err(format!(
-                            "element {} ({}) has null density but category requires a density",
-                            id, &el.key
-                        ))
+                        report.error(ContentError::MissingDensity {
+                            element: el.key.clone(),
+                            category: el.category.clone(),
+                        });
+                        0.0
                     }
                 },
             };
@@ -229,15 +315,29 @@ impl ContentRegistry {
 
             let behavior_kind = match el.behavior.as_deref() {
                 None => BehaviorKind::None,
-                Some(s) => behavior_kind_from_str(s)?,
+                Some(s) => behavior_kind_from_str(s).unwrap_or_else(|| {
+                    report.error(ContentError::UnknownBehavior {
+                        element: el.key.clone(),
+                        behavior: s.to_string(),
+                    });
+                    BehaviorKind::None
+                }),
             };
 
             let phase = match el.phase_change {
                 None => PhaseChange { high: None, low: None },
-                Some(pc) => PhaseChange {
-                    high: pc.high.map(|h| (h.temp as f32, h.to_id as ElementId)),
-                    low: pc.low.map(|l| (l.temp as f32, l.to_id as ElementId)),
-                },
+                Some(pc) => {
+                    if let Some(h) = &pc.high {
+                        phase_targets.push((el.key.clone(), "high", h.to_id));
+                    }
+                    if let Some(l) = &pc.low {
+                        phase_targets.push((el.key.clone(), "low", l.to_id));
+                    }
+                    PhaseChange {
+                        high: pc.high.map(|h| (h.temp as f32, h.to_id as ElementId)),
+                        low: pc.low.map(|l| (l.temp as f32, l.to_id as ElementId)),
+                    }
+                }
             };
 
             let props = ElementProps {
@@ -253,20 +353,13 @@ impl ContentRegistry {
                 friction: el.friction as f32,
             };
 
-            let idx = id as usize;
-            if idx >= props_by_id.len() {
-                return Err(format!("element id out of range: {}", idx));
-            }
-            if props_by_id[idx].is_some() {
-                return Err(format!("duplicate element id: {}", idx));
-            }
-
             props_by_id[idx] = Some(props);
             behavior_by_id[idx] = Some(behavior_kind);
             phase_by_id[idx] = Some(phase);
+            tags_by_id[idx] = el.tags.clone();
 
             let key = el.key;
-            element_key_to_id.insert(key.clone(), id);
+            element_key_to_id.insert(key.clone(), el.id);
 
             let ui = el.ui.map(|ui| ContentManifestElementUi {
                 category: ui.category,
@@ -284,92 +377,226 @@ impl ContentRegistry {
                 color: el.color,
                 hidden: el.hidden,
                 ui,
+                tags: el.tags,
             });
         }
 
-        if !matches!(props_by_id.get(EL_EMPTY as usize).and_then(|v| *v), Some(_)) {
-            return Err("missing element id 0 (empty)".to_string());
+        if len == 0 {
+            report.error(ContentError::MissingElementId { id: 0 });
+        }
+        for (idx, meta) in manifest_by_id.iter().enumerate() {
+            if meta.is_none() {
+                report.error(ContentError::MissingElementId { id: idx as u16 });
+            }
         }
+        let exists = |id: u16| manifest_by_id.get(id as usize).is_some_and(Option::is_some);
+        // Unknown ids print as `#id` in reaction labels
+        let key_of = |id: u16| match manifest_by_id.get(id as usize) {
+            Some(Some(meta)) => meta.key.clone(),
+            _ => format!("#{}", id),
+        };
 
-        for (k, v) in bundle.element_key_to_id.into_iter() {
-            let id = v as u16;
-            if id > (u8::MAX as u16) {
-                return Err(format!("elementKeyToId contains u16 id not supported yet: {}", id));
+        for (element, field, id) in phase_targets {
+            if !exists(id) {
+                report.error(ContentError::UnknownPhaseTarget { element, field, id });
             }
-            let id8 = id as ElementId;
+        }
+
+        let mut key_map: Vec<(String, u16)> = bundle.element_key_to_id.into_iter().collect();
+        key_map.sort();
+        for (k, id) in key_map {
             match element_key_to_id.get(&k) {
-                Some(existing) if *existing == id8 => {}
+                Some(existing) if *existing == id => {}
                 Some(existing) => {
-                    return Err(format!(
-                        "elementKeyToId mismatch for key {}: map={} elements={}",
-                        k, id8, existing
-                    ));
+                    report.error(ContentError::KeyMapMismatch {
+                        key: k,
+                        map_id: id,
+                        element_id: *existing,
+                    });
                 }
+                None if !exists(id) => report.error(ContentError::KeyMapUnknownId { key: k, id }),
                 None => {
-                    element_key_to_id.insert(k, id8);
+                    element_key_to_id.insert(k, id);
                 }
             }
         }
 
-        let mut elements = Vec::with_capacity(props_by_id.len());
-        let mut behavior_kind_by_id = Vec::with_capacity(props_by_id.len());
-        let mut phase_changes = Vec::with_capacity(props_by_id.len());
-        let mut element_manifest = Vec::with_capacity(props_by_id.len());
-
-        for idx in 0..props_by_id.len() {
-            let props = props_by_id[idx]
-                .ok_or_else(|| format!("missing element id {}", idx))?;
-            let kind = behavior_by_id[idx]
-                .unwrap_or(BehaviorKind::None);
-            let phase = phase_by_id[idx]
-                .unwrap_or(PhaseChange { high: None, low: None });
-            let meta = manifest_by_id[idx]
-                .clone()
-                .ok_or_else(|| format!("missing element manifest for id {}", idx))?;
-
-            elements.push(props);
-            behavior_kind_by_id.push(kind);
-            phase_changes.push(phase);
-            element_manifest.push(meta);
+        // Elements something can turn into, besides the palette
+        let mut produced = vec![false; len];
+        for phase in phase_by_id.iter().flatten() {
+            for (_, to) in phase.high.iter().chain(phase.low.iter()) {
+                if let Some(p) = produced.get_mut(*to as usize) {
+                    *p = true;
+                }
+            }
         }
 
-        let mut reaction_lut = vec![None; REACTION_LUT_SIZE];
+        let mut reactions = ReactionSystem::default();
+        // Outcomes and summed chance seen so far, per pair
+        let mut pairs: HashMap<(u16, u16), (Vec<ReactionOutcome>, f64)> = HashMap::new();
+        // Wildcard reactions, expanded once every explicit pair is known
+        let mut wildcards: Vec<(String, ReactionSide, ReactionSide, Reaction, f64)> = Vec::new();
         for r in bundle.reactions.into_iter() {
-            let aggressor = r.aggressor_id as ElementId;
-            let victim = r.victim_id as ElementId;
-
-            let target_becomes = r.result_victim_id as ElementId;
-            let source_becomes = match r.result_aggressor_id {
-                None => Reaction::NO_CHANGE,
-                Some(v) => v as u8,
+            let side_label = |id: Option<u16>, matcher: &Option<BundleElementMatch>| match (id, matcher) {
+                (Some(id), _) => key_of(id),
+                (None, Some(m)) => m.label(),
+                (None, None) => "?".to_string(),
+            };
+            let label = format!(
+                "{} + {}",
+                side_label(r.aggressor_id, &r.aggressor_match),
+                side_label(r.victim_id, &r.victim_match)
+            );
+            let mut side = |side: &'static str, id: Option<u16>, matcher: Option<BundleElementMatch>| {
+                let resolved = match (id, matcher) {
+                    (Some(id), None) => Ok(ReactionSide::Id(id)),
+                    (None, Some(m)) => m.resolve(&label),
+                    _ => Err(ContentError::InvalidReactionSide { reaction: label.clone(), side }),
+                };
+                resolved.map_err(|e| report.error(e)).ok()
             };
+            let aggressor = side("aggressor", r.aggressor_id, r.aggressor_match);
+            let victim = side("victim", r.victim_id, r.victim_match);
+            let mut valid = aggressor.is_some() && victim.is_some();
+
+            let refs = [
+                ("aggressorId", r.aggressor_id),
+                ("victimId", r.victim_id),
+                ("resultAggressorId", r.result_aggressor_id),
+                ("resultVictimId", Some(r.result_victim_id)),
+                ("spawnId", r.spawn_id),
+            ];
+            for (field, id) in refs {
+                let Some(id) = id else { continue };
+                if exists(id) {
+                    produced[id as usize] |= field != "aggressorId" && field != "victimId";
+                } else {
+                    report.error(ContentError::UnknownReactionElement {
+                        reaction: label.clone(),
+                        field,
+                        id,
+                    });
+                    valid = false;
+                }
+            }
+            if !(0.0..=1.0).contains(&r.chance) {
+                report.error(ContentError::InvalidReactionChance { reaction: label.clone(), chance: r.chance });
+                valid = false;
+            }
+            let (true, Some(aggressor), Some(victim)) = (valid, aggressor, victim) else {
+                continue;
+            };
+            if let (Some(min_temp), Some(max_temp)) = (r.min_temp, r.max_temp) {
+                if min_temp > max_temp {
+                    report.error(ContentError::InvalidReactionTempRange { reaction: label, min_temp, max_temp });
+                    continue;
+                }
+            }
 
             let chance = chance_to_u8(r.chance);
-
-            let spawn = r.spawn_id.map(|v| v as ElementId).unwrap_or(EL_EMPTY);
+            if chance == 0 {
+                report.warn(ContentWarning::ZeroChanceReaction { reaction: label.clone() });
+            }
 
             let reaction = Reaction {
-                target_becomes,
-                source_becomes,
+                target_becomes: r.result_victim_id as ElementId,
+                source_becomes: match r.result_aggressor_id {
+                    None => Reaction::NO_CHANGE,
+                    Some(v) => v as ElementId,
+                },
                 chance,
-                spawn,
+                spawn: r.spawn_id.map(|v| v as ElementId).unwrap_or(EL_EMPTY),
+                min_temp: r.min_temp.map_or(f32::NEG_INFINITY, |t| t as f32),
+                max_temp: r.max_temp.map_or(f32::INFINITY, |t| t as f32),
+                heat_delta: r.heat_delta as f32,
+            };
+
+            let (ReactionSide::Id(aggressor_id), ReactionSide::Id(victim_id)) = (&aggressor, &victim) else {
+                wildcards.push((label, aggressor, victim, reaction, r.chance));
+                continue;
             };
+            let (aggressor_id, victim_id) = (*aggressor_id, *victim_id);
+
+            // Later candidates for a pair share the roll with earlier ones
+            let (outcomes, total) = pairs.entry((aggressor_id, victim_id)).or_default();
+            let outcome = (r.result_aggressor_id, r.result_victim_id, r.spawn_id, r.min_temp, r.max_temp);
+            if outcomes.contains(&outcome) {
+                report.warn(ContentWarning::DuplicateReaction { reaction: label.clone() });
+            }
+            outcomes.push(outcome);
+            if add_chance(total, r.chance) {
+                report.warn(ContentWarning::ReactionChanceOverflow { reaction: label });
+            }
+
+            reactions.add(aggressor_id as ElementId, victim_id as ElementId, reaction);
+        }
+
+        // Wildcards fill in every matching pair that has no explicit reaction
+        let matching = |side: &ReactionSide| -> Vec<u16> {
+            (0..len)
+                .filter(|&id| {
+                    let Some(props) = &props_by_id[id] else { return false };
+                    match side {
+                        ReactionSide::Id(x) => id == *x as usize,
+                        _ if id == EL_EMPTY as usize => false,
+                        ReactionSide::Category(c) => props.category == *c,
+                        ReactionSide::Flag(f) => props.flags & f != 0,
+                        ReactionSide::Tag(t) => tags_by_id[id].contains(t),
+                    }
+                })
+                .map(|id| id as u16)
+                .collect()
+        };
+        // Summed chance per expanded pair: overlapping wildcards share its roll
+        let mut expanded_totals: HashMap<(u16, u16), f64> = HashMap::new();
+        for (label, aggressor, victim, reaction, chance) in wildcards {
+            let victims = matching(&victim);
+            let mut expanded = 0usize;
+            let mut overflow = false;
+            for a in matching(&aggressor) {
+                for &v in &victims {
+                    if !pairs.contains_key(&(a, v)) {
+                        overflow |= add_chance(expanded_totals.entry((a, v)).or_default(), chance);
+                        reactions.add(a as ElementId, v as ElementId, reaction);
+                        expanded += 1;
+                    }
+                }
+            }
+            if overflow {
+                report.warn(ContentWarning::ReactionChanceOverflow { reaction: label.clone() });
+            }
+            if expanded == 0 {
+                report.warn(ContentWarning::WildcardMatchesNothing { reaction: label });
+            }
+        }
 
-            let idx = ((aggressor as usize) << 8) | (victim as usize);
-            if idx >= reaction_lut.len() {
-                return Err(format!("reaction idx out of range: {}", idx));
+        for (idx, meta) in manifest_by_id.iter().enumerate() {
+            if let Some(meta) = meta {
+                if idx != EL_EMPTY as usize && meta.hidden && !produced[idx] {
+                    report.warn(ContentWarning::UnreachableElement { element: meta.key.clone() });
+                }
             }
-            reaction_lut[idx] = Some(reaction);
         }
 
-        Ok(Self {
+        if !report.is_ok() {
+            return (None, report);
+        }
+
+        // No errors: every id in 0..len is present
+        let elements = props_by_id.into_iter().flatten().collect();
+        let behavior_kind_by_id = behavior_by_id.into_iter().flatten().collect();
+        let phase_changes = phase_by_id.into_iter().flatten().collect();
+        let element_manifest = manifest_by_id.into_iter().flatten().collect();
+
+        let registry = Self {
             elements,
             behavior_kind_by_id,
             phase_changes,
-            reaction_lut,
-            element_key_to_id,
+            reactions,
+            element_key_to_id: element_key_to_id.into_iter().map(|(k, id)| (k, id as ElementId)).collect(),
             element_manifest,
-        })
+        };
+        (Some(registry), report)
     }
 }
 
@@ -393,6 +620,8 @@ pub struct ContentManifestElement {
     hidden: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     ui: Option<ContentManifestElementUi>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
 }
 
 #[derive(Clone, Serialize)]
@@ -405,40 +634,94 @@ pub struct ContentManifestElementUi {
     hidden: bool,
 }
 
+/// A tunable element property with a value checked against the bundle field type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ElementProperty {
+    Density(f32),
+    Dispersion(u8),
+    Friction(f32),
+    Bounce(f32),
+    HeatConductivity(u8),
+    Color(u32),
+}
+
+impl ElementProperty {
+    /// Parse a bundle field name (`density`, `dispersion`, `friction`, `bounce`,
+    /// `heatConductivity`, `color`) and a JS number
+    pub fn parse(field: &str, value: f64) -> Result<Self, String> {
+        if !value.is_finite() {
+            return Err(format!("{} must be a finite number: {}", field, value));
+        }
+        match field {
+            "density" => Ok(Self::Density(value as f32)),
+            "dispersion" => Ok(Self::Dispersion(integer_field(field, value)?)),
+            "friction" => Ok(Self::Friction(value as f32)),
+            "bounce" => Ok(Self::Bounce(value as f32)),
+            "heatConductivity" => Ok(Self::HeatConductivity(integer_field(field, value)?)),
+            "color" => Ok(Self::Color(integer_field(field, value)?)),
+            _ => Err(format!("unknown element property: {}", field)),
+        }
+    }
+}
+
+/// Same range the bundle's integer field types accept
+fn integer_field<T: TryFrom<i64>>(field: &str, value: f64) -> Result<T, String> {
+    if value.fract() != 0.0 {
+        return Err(format!("{} must be an integer: {}", field, value));
+    }
+    T::try_from(value as i64).map_err(|_| format!("{} out of range: {}", field, value))
+}
+
+/// Add a listed chance to a pair's total; true when this pushes it past 1.
+/// Compares the listed chances: rounding to 0-255 can overshoot by one.
+fn add_chance(total: &mut f64, chance: f64) -> bool {
+    let was_full = *total > 1.0 + 1e-9;
+    *total += chance;
+    *total > 1.0 + 1e-9 && !was_full
+}
+
+/// `chance` is already checked to be in 0..=1
 fn chance_to_u8(chance: f64) -> u8 {
-    let v = (chance * 255.0).round();
-    if v <= 0.0 {
-        0
-    } else if v >= 255.0 {
-        255
-    } else {
-        v as u8
+    (chance * 255.0).round() as u8
+}
+
+fn behavior_kind_from_str(s: &str) -> Option<BehaviorKind> {
+    match s {
+        "bio_plant" => Some(BehaviorKind::BioPlant),
+        "bio_seed" => Some(BehaviorKind::BioSeed),
+        "energy_electricity" => Some(BehaviorKind::EnergyElectricity),
+        "energy_fire" => Some(BehaviorKind::EnergyFire),
+        "energy_spark" => Some(BehaviorKind::EnergySpark),
+        "utility_clone" => Some(BehaviorKind::UtilityClone),
+        "utility_void" => Some(BehaviorKind::UtilityVoid),
+        _ => None,
     }
 }
 
-fn behavior_kind_from_str(s: &str) -> Result<BehaviorKind, String> {
+/// Bundle flag names (the `flags` object of an element)
+fn flag_from_str(s: &str) -> Option<ElementFlags> {
     match s {
-        "bio_plant" => Ok(BehaviorKind::BioPlant),
-        "bio_seed" => Ok(BehaviorKind::BioSeed),
-        "energy_electricity" => Ok(BehaviorKind::EnergyElectricity),
-        "energy_fire" => Ok(BehaviorKind::EnergyFire),
-        "energy_spark" => Ok(BehaviorKind::EnergySpark),
-        "utility_clone" => Ok(BehaviorKind::UtilityClone),
-        "utility_void" => Ok(BehaviorKind::UtilityVoid),
-        _ => Err(format!("unknown behavior kind: {}", s)),
+        "flammable" => Some(FLAG_FLAMMABLE),
+        "conductive" => Some(FLAG_CONDUCTIVE),
+        "corrosive" => Some(FLAG_CORROSIVE),
+        "hot" => Some(FLAG_HOT),
+        "cold" => Some(FLAG_COLD),
+        "ignoreGravity" => Some(FLAG_IGNORE_GRAVITY),
+        "rigid" => Some(FLAG_RIGID),
+        _ => None,
     }
 }
 
-fn category_from_str(s: &str) -> Result<(CategoryId, ElementFlags), String> {
+fn category_from_str(s: &str) -> Option<(CategoryId, ElementFlags)> {
     match s {
-        "solid" => Ok((CAT_SOLID, FLAG_SOLID)),
-        "powder" => Ok((CAT_POWDER, FLAG_POWDER)),
-        "liquid" => Ok((CAT_LIQUID, FLAG_LIQUID)),
-        "gas" => Ok((CAT_GAS, FLAG_GAS)),
-        "energy" => Ok((CAT_ENERGY, FLAG_ENERGY)),
-        "utility" => Ok((CAT_UTILITY, FLAG_UTILITY)),
-        "bio" => Ok((CAT_BIO, FLAG_BIO)),
-        _ => Err(format!("unknown category: {}", s)),
+        "solid" => Some((CAT_SOLID, FLAG_SOLID)),
+        "powder" => Some((CAT_POWDER, FLAG_POWDER)),
+        "liquid" => Some((CAT_LIQUID, FLAG_LIQUID)),
+        "gas" => Some((CAT_GAS, FLAG_GAS)),
+        "energy" => Some((CAT_ENERGY, FLAG_ENERGY)),
+        "utility" => Some((CAT_UTILITY, FLAG_UTILITY)),
+        "bio" => Some((CAT_BIO, FLAG_BIO)),
+        _ => None,
     }
 }
 
@@ -477,6 +760,9 @@ struct BundleElement {
     hidden: bool,
     #[serde(default)]
     ui: Option<BundleElementUi>,
+    /// Free-form labels that wildcard reactions can match
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 #[derive(Clone, Deserialize)]
@@ -521,13 +807,73 @@ struct BundlePhaseEndpoint {
     to_id: u16,
 }
 
+/// `(resultAggressorId, resultVictimId, spawnId, minTemp, maxTemp)` of a bundle reaction
+type ReactionOutcome = (Option<u16>, u16, Option<u16>, Option<f64>, Option<f64>);
+
+/// One side of a reaction: a concrete element or every element matching a wildcard
+enum ReactionSide {
+    Id(u16),
+    Category(CategoryId),
+    Flag(ElementFlags),
+    Tag(String),
+}
+
+/// Wildcard reaction side: `{"category": "powder"}`, `{"flag": "flammable"}`
+/// or `{"tag": "organic"}`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum BundleElementMatch {
+    Category(String),
+    Flag(String),
+    Tag(String),
+}
+
+impl BundleElementMatch {
+    fn label(&self) -> String {
+        match self {
+            Self::Category(c) => format!("category:{}", c),
+            Self::Flag(f) => format!("flag:{}", f),
+            Self::Tag(t) => format!("tag:{}", t),
+        }
+    }
+
+    fn resolve(self, reaction: &str) -> Result<ReactionSide, ContentError> {
+        match self {
+            Self::Category(category) => match category_from_str(&category) {
+                Some((id, _)) => Ok(ReactionSide::Category(id)),
+                None => Err(ContentError::UnknownMatchCategory { reaction: reaction.to_string(), category }),
+            },
+            Self::Flag(flag) => match flag_from_str(&flag) {
+                Some(bit) => Ok(ReactionSide::Flag(bit)),
+                None => Err(ContentError::UnknownMatchFlag { reaction: reaction.to_string(), flag }),
+            },
+            Self::Tag(tag) => Ok(ReactionSide::Tag(tag)),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct BundleReaction {
-    aggressor_id: u16,
-    victim_id: u16,
+    /// Exactly one of `aggressorId` / `aggressorMatch` (same for the victim)
+    #[serde(default)]
+    aggressor_id: Option<u16>,
+    #[serde(default)]
+    aggressor_match: Option<BundleElementMatch>,
+    #[serde(default)]
+    victim_id: Option<u16>,
+    #[serde(default)]
+    victim_match: Option<BundleElementMatch>,
     result_aggressor_id: Option<u16>,
     result_victim_id: u16,
     spawn_id: Option<u16>,
     chance: f64,
+    /// Temperature gate (mean of both cells, °C); absent = no limit
+    #[serde(default)]
+    min_temp: Option<f64>,
+    #[serde(default)]
+    max_temp: Option<f64>,
+    /// Added to both cells when the reaction fires
+    #[serde(default)]
+    heat_delta: f64,
 }
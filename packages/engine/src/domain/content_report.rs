@@ -0,0 +1,173 @@
+//! Structured result of validating a content bundle.
+//!
+//! Errors make a bundle unloadable; warnings point at content that loads but
+//! probably does not do what the author intended. Both serialize to JSON
+//! (`{"kind": "...", ...}`) so ModStudio can list every problem at once.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::elements::Reaction;
+
+/// A problem that prevents the bundle from loading
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ContentError {
+    /// The JSON does not match the bundle format
+    Parse { message: String },
+    /// Element ids must be below `Reaction::NO_CHANGE` (255, or 65535 with `wide-element-ids`)
+    ElementIdOutOfRange { element: String, id: u16 },
+    DuplicateElementId { element: String, id: u16, existing: String },
+    DuplicateElementKey { element: String },
+    /// Ids must be contiguous from 0 (empty)
+    MissingElementId { id: u16 },
+    UnknownCategory { element: String, category: String },
+    UnknownBehavior { element: String, behavior: String },
+    /// Only utility, energy and bio elements may omit `density`
+    MissingDensity { element: String, category: String },
+    /// `phaseChange.high`/`low` points at an element that does not exist
+    UnknownPhaseTarget { element: String, field: &'static str, id: u16 },
+    /// `elementKeyToId` entry points at an element that does not exist
+    KeyMapUnknownId { key: String, id: u16 },
+    /// `elementKeyToId` disagrees with the element's own id
+    KeyMapMismatch { key: String, map_id: u16, element_id: u16 },
+    /// A reaction id field points at an element that does not exist
+    UnknownReactionElement { reaction: String, field: &'static str, id: u16 },
+    /// `chance` is NaN or outside 0..=1
+    InvalidReactionChance { reaction: String, chance: f64 },
+    /// `minTemp` above `maxTemp`: the reaction could never fire
+    InvalidReactionTempRange { reaction: String, min_temp: f64, max_temp: f64 },
+    /// A reaction side needs exactly one of `<side>Id` and `<side>Match`
+    InvalidReactionSide { reaction: String, side: &'static str },
+    /// Wildcard `{"category": ...}` names an unknown category
+    UnknownMatchCategory { reaction: String, category: String },
+    /// Wildcard `{"flag": ...}` names an unknown element flag
+    UnknownMatchFlag { reaction: String, flag: String },
+}
+
+/// Content that loads but is likely a mistake
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ContentWarning {
+    /// Hidden from palettes and not produced by any phase change or reaction
+    UnreachableElement { element: String },
+    /// `chance` rounds to 0, so the reaction never fires
+    ZeroChanceReaction { reaction: String },
+    /// Same pair and same outcome as an earlier reaction; both are kept, so
+    /// their chances add up
+    DuplicateReaction { reaction: String },
+    /// The chances of all reactions for this pair add up to more than 1, so
+    /// the later ones fire less often than listed
+    ReactionChanceOverflow { reaction: String },
+    /// A wildcard reaction matches no pair without an explicit reaction
+    WildcardMatchesNothing { reaction: String },
+}
+
+impl fmt::Display for ContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse { message } => write!(f, "invalid bundle: {}", message),
+            Self::ElementIdOutOfRange { element, id } => {
+                write!(f, "element {}: id {} is out of range (max {})", element, id, Reaction::NO_CHANGE - 1)
+            }
+            Self::DuplicateElementId { element, id, existing } => {
+                write!(f, "element {}: duplicate element id {} (already used by {})", element, id, existing)
+            }
+            Self::DuplicateElementKey { element } => write!(f, "duplicate element key: {}", element),
+            Self::MissingElementId { id: 0 } => write!(f, "missing element id 0 (empty)"),
+            Self::MissingElementId { id } => write!(f, "missing element id {}", id),
+            Self::UnknownCategory { element, category } => {
+                write!(f, "element {}: unknown category: {}", element, category)
+            }
+            Self::UnknownBehavior { element, behavior } => {
+                write!(f, "element {}: unknown behavior kind: {}", element, behavior)
+            }
+            Self::MissingDensity { element, category } => {
+                write!(f, "element {}: null density but category {} requires a density", element, category)
+            }
+            Self::UnknownPhaseTarget { element, field, id } => {
+                write!(f, "element {}: phaseChange.{} turns into unknown element id {}", element, field, id)
+            }
+            Self::KeyMapUnknownId { key, id } => {
+                write!(f, "elementKeyToId[{}] points at unknown element id {}", key, id)
+            }
+            Self::KeyMapMismatch { key, map_id, element_id } => write!(
+                f,
+                "elementKeyToId mismatch for key {}: map={} elements={}",
+                key, map_id, element_id
+            ),
+            Self::UnknownReactionElement { reaction, field, id } => {
+                write!(f, "reaction {}: {} is unknown element id {}", reaction, field, id)
+            }
+            Self::InvalidReactionChance { reaction, chance } => {
+                write!(f, "reaction {}: chance {} is not between 0 and 1", reaction, chance)
+            }
+            Self::InvalidReactionTempRange { reaction, min_temp, max_temp } => {
+                write!(f, "reaction {}: minTemp {} is above maxTemp {}", reaction, min_temp, max_temp)
+            }
+            Self::InvalidReactionSide { reaction, side } => {
+                write!(f, "reaction {}: needs exactly one of {}Id and {}Match", reaction, side, side)
+            }
+            Self::UnknownMatchCategory { reaction, category } => {
+                write!(f, "reaction {}: unknown category: {}", reaction, category)
+            }
+            Self::UnknownMatchFlag { reaction, flag } => write!(f, "reaction {}: unknown flag: {}", reaction, flag),
+        }
+    }
+}
+
+impl fmt::Display for ContentWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnreachableElement { element } => {
+                write!(f, "element {} is hidden and never produced by a phase change or reaction", element)
+            }
+            Self::ZeroChanceReaction { reaction } => write!(f, "reaction {} has chance 0 and never fires", reaction),
+            Self::DuplicateReaction { reaction } => {
+                write!(f, "reaction {} has the same outcome as an earlier reaction for the pair", reaction)
+            }
+            Self::ReactionChanceOverflow { reaction } => {
+                write!(f, "reactions {}: chances add up to more than 1", reaction)
+            }
+            Self::WildcardMatchesNothing { reaction } => {
+                write!(f, "wildcard reaction {} matches no element pair without an explicit reaction", reaction)
+            }
+        }
+    }
+}
+
+/// Every error and warning found in one bundle
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ContentReport {
+    pub errors: Vec<ContentError>,
+    pub warnings: Vec<ContentWarning>,
+}
+
+impl ContentReport {
+    /// True when the bundle can be loaded (warnings allowed)
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    pub(crate) fn error(&mut self, error: ContentError) {
+        self.errors.push(error);
+    }
+
+    pub(crate) fn warn(&mut self, warning: ContentWarning) {
+        self.warnings.push(warning);
+    }
+
+    /// One-line message for `String` error paths: the first error and how many follow
+    pub(crate) fn error_summary(&self) -> String {
+        match self.errors.as_slice() {
+            [] => String::new(),
+            [only] => only.to_string(),
+            [first, rest @ ..] => format!("{} (and {} more errors)", first, rest.len()),
+        }
+    }
+}
@@ -8,8 +8,11 @@
 
 use wasm_bindgen::prelude::*;
 
-/// Element ID as u8 for compact storage
+/// Element ID as u8 for compact storage (u16 with the `wide-element-ids` feature)
+#[cfg(not(feature = "wide-element-ids"))]
 pub type ElementId = u8;
+#[cfg(feature = "wide-element-ids")]
+pub type ElementId = u16;
 
 // ============================================================================
 // ELEMENT CONSTANTS
@@ -516,84 +519,132 @@ pub fn check_phase_change(element: ElementId, temp: f32) -> Option<ElementId> {
 }
 
 // ============================================================================
-// REACTION LOOKUP TABLE (LUT) - O(1) Access
+// REACTION TABLE - sparse, per aggressor
 // ============================================================================
 
-/// Reaction result from LUT
+/// Reaction result
 #[derive(Clone, Copy, Debug)]
 pub struct Reaction {
     /// What victim becomes (EL_EMPTY = destroyed)
     pub target_becomes: ElementId,
-    /// What aggressor becomes (255 = unchanged, EL_EMPTY = destroyed)
-    pub source_becomes: u8,
+    /// What aggressor becomes (NO_CHANGE = unchanged, EL_EMPTY = destroyed)
+    pub source_becomes: ElementId,
     /// Probability 0-255 (255 = 100%)
     pub chance: u8,
     /// Spawn byproduct (EL_EMPTY = none)
     pub spawn: ElementId,
+    /// Fires only when the mean temperature of both cells is in
+    /// `min_temp..=max_temp` (infinite = no limit). The mean keeps the gate
+    /// symmetric: a hot aggressor next to a cold victim counts the same as the reverse.
+    pub min_temp: f32,
+    pub max_temp: f32,
+    /// Degrees added to both cells when it fires (exothermic > 0, endothermic < 0)
+    pub heat_delta: f32,
 }
 
 impl Reaction {
-    pub const NO_CHANGE: u8 = 255;
+    /// Reserved id: never a valid element
+    pub const NO_CHANGE: ElementId = ElementId::MAX;
+    
+    /// Temperature gate check
+    #[inline]
+    pub fn accepts_temp(&self, temp: f32) -> bool {
+        temp >= self.min_temp && temp <= self.max_temp
+    }
 }
 
-/// Reaction LUT size: 256 * 256 = 65536 entries
-pub const REACTION_LUT_SIZE: usize = 65536;
-
-/// Reaction LUT initialization data (sparse): (index, reaction)
-pub static REACTION_INIT_DATA: [(usize, Reaction); 22] = [
-    (1544, Reaction { target_becomes: 1, source_becomes: 10, chance: 38, spawn: 10 }), // water_lava
-    (1548, Reaction { target_becomes: 0, source_becomes: 10, chance: 77, spawn: 0 }), // water_fire
-    (2051, Reaction { target_becomes: 12, source_becomes: 255, chance: 77, spawn: 11 }), // lava_wood
-    (2053, Reaction { target_becomes: 10, source_becomes: 1, chance: 77, spawn: 0 }), // lava_ice
-    (2054, Reaction { target_becomes: 10, source_becomes: 1, chance: 38, spawn: 10 }), // lava_water
-    (2055, Reaction { target_becomes: 12, source_becomes: 255, chance: 102, spawn: 11 }), // lava_oil
-    (2063, Reaction { target_becomes: 12, source_becomes: 255, chance: 255, spawn: 11 }), // lava_gunpowder
-    (2066, Reaction { target_becomes: 1, source_becomes: 255, chance: 13, spawn: 0 }), // lava_dirt
-    (2068, Reaction { target_becomes: 12, source_becomes: 255, chance: 128, spawn: 11 }), // lava_plant
-    (2305, Reaction { target_becomes: 0, source_becomes: 0, chance: 26, spawn: 11 }), // acid_stone
-    (2307, Reaction { target_becomes: 0, source_becomes: 0, chance: 51, spawn: 0 }), // acid_wood
-    (2308, Reaction { target_becomes: 0, source_becomes: 0, chance: 13, spawn: 0 }), // acid_metal
-    (2309, Reaction { target_becomes: 6, source_becomes: 0, chance: 51, spawn: 0 }), // acid_ice
-    (2322, Reaction { target_becomes: 0, source_becomes: 0, chance: 13, spawn: 0 }), // acid_dirt
-    (2324, Reaction { target_becomes: 0, source_becomes: 0, chance: 38, spawn: 0 }), // acid_plant
-    (3075, Reaction { target_becomes: 12, source_becomes: 11, chance: 26, spawn: 11 }), // fire_wood
-    (3077, Reaction { target_becomes: 6, source_becomes: 0, chance: 77, spawn: 10 }), // fire_ice
-    (3078, Reaction { target_becomes: 10, source_becomes: 0, chance: 128, spawn: 0 }), // fire_water
-    (3079, Reaction { target_becomes: 12, source_becomes: 11, chance: 51, spawn: 11 }), // fire_oil
-    (3087, Reaction { target_becomes: 12, source_becomes: 12, chance: 255, spawn: 11 }), // fire_gunpowder
-    (3091, Reaction { target_becomes: 12, source_becomes: 11, chance: 51, spawn: 0 }), // fire_seed
-    (3092, Reaction { target_becomes: 12, source_becomes: 11, chance: 26, spawn: 11 }), // fire_plant
+/// Built-in reactions (sparse): (aggressor, victim, reaction)
+pub static REACTION_INIT_DATA: [(ElementId, ElementId, Reaction); 22] = [
+    (6, 8, Reaction { target_becomes: 1, source_becomes: 10, chance: 38, spawn: 10, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // water_lava
+    (6, 12, Reaction { target_becomes: 0, source_becomes: 10, chance: 77, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // water_fire
+    (8, 3, Reaction { target_becomes: 12, source_becomes: Reaction::NO_CHANGE, chance: 77, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_wood
+    (8, 5, Reaction { target_becomes: 10, source_becomes: 1, chance: 77, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_ice
+    (8, 6, Reaction { target_becomes: 10, source_becomes: 1, chance: 38, spawn: 10, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_water
+    (8, 7, Reaction { target_becomes: 12, source_becomes: Reaction::NO_CHANGE, chance: 102, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_oil
+    (8, 15, Reaction { target_becomes: 12, source_becomes: Reaction::NO_CHANGE, chance: 255, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_gunpowder
+    (8, 18, Reaction { target_becomes: 1, source_becomes: Reaction::NO_CHANGE, chance: 13, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_dirt
+    (8, 20, Reaction { target_becomes: 12, source_becomes: Reaction::NO_CHANGE, chance: 128, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // lava_plant
+    (9, 1, Reaction { target_becomes: 0, source_becomes: 0, chance: 26, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // acid_stone
+    (9, 3, Reaction { target_becomes: 0, source_becomes: 0, chance: 51, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // acid_wood
+    (9, 4, Reaction { target_becomes: 0, source_becomes: 0, chance: 13, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // acid_metal
+    (9, 5, Reaction { target_becomes: 6, source_becomes: 0, chance: 51, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // acid_ice
+    (9, 18, Reaction { target_becomes: 0, source_becomes: 0, chance: 13, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // acid_dirt
+    (9, 20, Reaction { target_becomes: 0, source_becomes: 0, chance: 38, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // acid_plant
+    (12, 3, Reaction { target_becomes: 12, source_becomes: 11, chance: 26, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_wood
+    (12, 5, Reaction { target_becomes: 6, source_becomes: 0, chance: 77, spawn: 10, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_ice
+    (12, 6, Reaction { target_becomes: 10, source_becomes: 0, chance: 128, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_water
+    (12, 7, Reaction { target_becomes: 12, source_becomes: 11, chance: 51, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_oil
+    (12, 15, Reaction { target_becomes: 12, source_becomes: 12, chance: 255, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_gunpowder
+    (12, 19, Reaction { target_becomes: 12, source_becomes: 11, chance: 51, spawn: 0, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_seed
+    (12, 20, Reaction { target_becomes: 12, source_becomes: 11, chance: 26, spawn: 11, min_temp: f32::NEG_INFINITY, max_temp: f32::INFINITY, heat_delta: 0.0 }), // fire_plant
 ];
 
-/// Reaction System with O(1) lookup
+/// Sparse reaction table: one list per aggressor, sorted by victim.
+/// Memory grows with the number of reactions, not with element count squared.
+/// A pair may have several candidate reactions; one roll picks at most one.
+#[derive(Clone, Debug, Default)]
 pub struct ReactionSystem {
-    lut: [Option<Reaction>; REACTION_LUT_SIZE],
+    by_aggressor: Vec<Vec<(ElementId, Vec<Reaction>)>>,
 }
 
 impl ReactionSystem {
     /// Create new reaction system from init data
     pub fn new() -> Self {
-        // Initialize with None
-        let mut lut = [None; REACTION_LUT_SIZE];
-        
-        // Populate from generated data
-        for (idx, reaction) in REACTION_INIT_DATA.iter() {
-            lut[*idx] = Some(*reaction);
+        let mut system = Self::default();
+        for (aggressor, victim, reaction) in REACTION_INIT_DATA.iter() {
+            system.add(*aggressor, *victim, *reaction);
         }
-        
-        Self { lut }
+        system
     }
     
-    /// O(1) reaction lookup - FAST!
+    /// Append a candidate reaction for a pair (after any existing ones)
+    pub fn add(&mut self, aggressor: ElementId, victim: ElementId, reaction: Reaction) {
+        let idx = aggressor as usize;
+        if idx >= self.by_aggressor.len() {
+            self.by_aggressor.resize_with(idx + 1, Vec::new);
+        }
+        let list = &mut self.by_aggressor[idx];
+        match list.binary_search_by_key(&victim, |(v, _)| *v) {
+            Ok(i) => list[i].1.push(reaction),
+            Err(i) => list.insert(i, (victim, vec![reaction])),
+        }
+    }
+    
+    /// Candidate reactions for a pair, in declaration order (binary search in the aggressor's list)
     #[inline(always)]
-    pub fn get(&self, aggressor: ElementId, victim: ElementId) -> Option<&Reaction> {
-        let idx = ((aggressor as usize) << 8) | (victim as usize);
-        // SAFETY: idx is always < 65536 since both IDs are u8
-        #[cfg(not(debug_assertions))]
-        unsafe { self.lut.get_unchecked(idx).as_ref() }
-        #[cfg(debug_assertions)]
-        self.lut[idx].as_ref()
+    pub fn get(&self, aggressor: ElementId, victim: ElementId) -> &[Reaction] {
+        let Some(list) = self.by_aggressor.get(aggressor as usize) else {
+            return &[];
+        };
+        match list.binary_search_by_key(&victim, |(v, _)| *v) {
+            Ok(i) => &list[i].1,
+            Err(_) => &[],
+        }
+    }
+    
+    /// Number of reactions (candidates of every pair)
+    pub fn len(&self) -> usize {
+        self.by_aggressor.iter().flatten().map(|(_, candidates)| candidates.len()).sum()
     }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Pick the outcome of one roll (0-255): candidates whose gate accepts `temp`
+/// own consecutive slices of the roll range, sized by their chance. With a
+/// single candidate this is `roll < chance`.
+#[inline]
+pub fn pick_reaction(candidates: &[Reaction], roll: u8, temp: f32) -> Option<&Reaction> {
+    let mut threshold = 0u32;
+    for reaction in candidates.iter().filter(|r| r.accepts_temp(temp)) {
+        threshold += reaction.chance as u32;
+        if (roll as u32) < threshold {
+            return Some(reaction);
+        }
+    }
+    None
 }
 
 /// Get element properties by ID
@@ -2,3 +2,4 @@ pub mod generated_elements;
 pub mod elements;
 pub mod elements_ext;
 pub mod content;
+pub mod content_report;
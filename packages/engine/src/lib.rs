@@ -62,55 +62,74 @@ pub fn version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Version of the shared-memory layout behind `abi_layout`.
+/// 2: `types_ptr` holds `element_id_bytes()` bytes per cell.
 #[wasm_bindgen]
 pub fn abi_version() -> u32 {
-    1
+    2
+}
+
+/// Validate a content bundle without loading it
+/// Returns `{"errors": [...], "warnings": [...]}`; each entry has a `kind` plus the
+/// offending element key, reaction (`aggressor + victim`) or id.
+#[wasm_bindgen]
+pub fn validate_content_bundle(json: &str) -> String {
+    domain::content::ContentRegistry::validate_bundle_json(json).to_json()
 }
 
 // Re-export main types
 pub use api::wasm::World;
 pub use domain::elements::ElementType;
 
+use domain::elements::ElementId;
+
+/// Bytes per element id: 1, or 2 in `wide-element-ids` builds
+/// JS must view `types_ptr` as `Uint8Array` or `Uint16Array` accordingly.
+#[wasm_bindgen]
+pub fn element_id_bytes() -> u32 {
+    std::mem::size_of::<ElementId>() as u32
+}
+
 // Export element constants for JS
 #[wasm_bindgen]
-pub fn el_empty() -> u8 { domain::elements::EL_EMPTY }
+pub fn el_empty() -> ElementId { domain::elements::EL_EMPTY }
 #[wasm_bindgen]
-pub fn el_stone() -> u8 { domain::elements::EL_STONE }
+pub fn el_stone() -> ElementId { domain::elements::EL_STONE }
 #[wasm_bindgen]
-pub fn el_sand() -> u8 { domain::elements::EL_SAND }
+pub fn el_sand() -> ElementId { domain::elements::EL_SAND }
 #[wasm_bindgen]
-pub fn el_wood() -> u8 { domain::elements::EL_WOOD }
+pub fn el_wood() -> ElementId { domain::elements::EL_WOOD }
 #[wasm_bindgen]
-pub fn el_metal() -> u8 { domain::elements::EL_METAL }
+pub fn el_metal() -> ElementId { domain::elements::EL_METAL }
 #[wasm_bindgen]
-pub fn el_ice() -> u8 { domain::elements::EL_ICE }
+pub fn el_ice() -> ElementId { domain::elements::EL_ICE }
 #[wasm_bindgen]
-pub fn el_water() -> u8 { domain::elements::EL_WATER }
+pub fn el_water() -> ElementId { domain::elements::EL_WATER }
 #[wasm_bindgen]
-pub fn el_oil() -> u8 { domain::elements::EL_OIL }
+pub fn el_oil() -> ElementId { domain::elements::EL_OIL }
 #[wasm_bindgen]
-pub fn el_lava() -> u8 { domain::elements::EL_LAVA }
+pub fn el_lava() -> ElementId { domain::elements::EL_LAVA }
 #[wasm_bindgen]
-pub fn el_acid() -> u8 { domain::elements::EL_ACID }
+pub fn el_acid() -> ElementId { domain::elements::EL_ACID }
 #[wasm_bindgen]
-pub fn el_steam() -> u8 { domain::elements::EL_STEAM }
+pub fn el_steam() -> ElementId { domain::elements::EL_STEAM }
 #[wasm_bindgen]
-pub fn el_smoke() -> u8 { domain::elements::EL_SMOKE }
+pub fn el_smoke() -> ElementId { domain::elements::EL_SMOKE }
 #[wasm_bindgen]
-pub fn el_fire() -> u8 { domain::elements::EL_FIRE }
+pub fn el_fire() -> ElementId { domain::elements::EL_FIRE }
 #[wasm_bindgen]
-pub fn el_spark() -> u8 { domain::elements::EL_SPARK }
+pub fn el_spark() -> ElementId { domain::elements::EL_SPARK }
 #[wasm_bindgen]
-pub fn el_electricity() -> u8 { domain::elements::EL_ELECTRICITY }
+pub fn el_electricity() -> ElementId { domain::elements::EL_ELECTRICITY }
 #[wasm_bindgen]
-pub fn el_gunpowder() -> u8 { domain::elements::EL_GUNPOWDER }
+pub fn el_gunpowder() -> ElementId { domain::elements::EL_GUNPOWDER }
 #[wasm_bindgen]
-pub fn el_clone() -> u8 { domain::elements::EL_CLONE }
+pub fn el_clone() -> ElementId { domain::elements::EL_CLONE }
 #[wasm_bindgen]
-pub fn el_void() -> u8 { domain::elements::EL_VOID }
+pub fn el_void() -> ElementId { domain::elements::EL_VOID }
 #[wasm_bindgen]
-pub fn el_dirt() -> u8 { domain::elements::EL_DIRT }
+pub fn el_dirt() -> ElementId { domain::elements::EL_DIRT }
 #[wasm_bindgen]
-pub fn el_seed() -> u8 { domain::elements::EL_SEED }
+pub fn el_seed() -> ElementId { domain::elements::EL_SEED }
 #[wasm_bindgen]
-pub fn el_plant() -> u8 { domain::elements::EL_PLANT }
+pub fn el_plant() -> ElementId { domain::elements::EL_PLANT }
@@ -0,0 +1,367 @@
+//! Shaped brushes: square, thick line, ring and polygon.
+//!
+//! A shape is rasterized into a set of in-bounds cells (each cell at most once),
+//! then every cell goes through the brush's placement mode.
+
+pub(crate) mod paint;
+
+use wasm_bindgen::prelude::*;
+
+use crate::elements::{CategoryId, ElementId, EL_EMPTY};
+
+/// Which existing cells a brush may write to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrushMode {
+    /// Only empty cells (classic brush)
+    FillEmpty,
+    /// Every cell, overwriting whatever is there
+    ReplaceAll,
+    /// Only cells holding this element
+    ReplaceElement(ElementId),
+    /// Only cells whose element belongs to this category
+    ReplaceCategory(CategoryId),
+}
+
+impl BrushMode {
+    /// Wire code: 0 = fill-empty, 1 = replace-all, 2 = replace-element, 3 = replace-category.
+    /// `target` is the element or category for codes 2 and 3.
+    pub fn from_code(code: u8, target: ElementId) -> Option<Self> {
+        match code {
+            0 => Some(Self::FillEmpty),
+            1 => Some(Self::ReplaceAll),
+            2 => Some(Self::ReplaceElement(target)),
+            3 => CategoryId::try_from(target as u32).ok().map(Self::ReplaceCategory),
+            _ => None,
+        }
+    }
+
+    /// `(code, target)` for `from_code`
+    pub fn code(self) -> (u8, ElementId) {
+        match self {
+            Self::FillEmpty => (0, 0),
+            Self::ReplaceAll => (1, 0),
+            Self::ReplaceElement(id) => (2, id),
+            Self::ReplaceCategory(cat) => (3, cat as ElementId),
+        }
+    }
+}
+
+/// Element to paint plus placement mode.
+/// Painting `EL_EMPTY` erases the cells the mode selects.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Brush {
+    element: ElementId,
+    mode: BrushMode,
+}
+
+#[wasm_bindgen]
+impl Brush {
+    /// Paint `element` into empty cells only
+    #[wasm_bindgen(constructor)]
+    pub fn new(element: ElementId) -> Self {
+        Self {
+            element,
+            mode: BrushMode::FillEmpty,
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn element(&self) -> ElementId {
+        self.element
+    }
+
+    pub fn set_fill_empty(&mut self) {
+        self.mode = BrushMode::FillEmpty;
+    }
+
+    pub fn set_replace_all(&mut self) {
+        self.mode = BrushMode::ReplaceAll;
+    }
+
+    /// Only overwrite cells holding `element`
+    pub fn set_replace_element(&mut self, element: ElementId) {
+        self.mode = BrushMode::ReplaceElement(element);
+    }
+
+    /// Only overwrite cells whose element is in `category`
+    pub fn set_replace_category(&mut self, category: CategoryId) {
+        self.mode = BrushMode::ReplaceCategory(category);
+    }
+}
+
+impl Brush {
+    pub fn with_mode(element: ElementId, mode: BrushMode) -> Self {
+        Self { element, mode }
+    }
+
+    pub fn mode(&self) -> BrushMode {
+        self.mode
+    }
+
+    /// Eraser brush
+    pub fn is_eraser(&self) -> bool {
+        self.element == EL_EMPTY
+    }
+}
+
+/// Brush footprint in grid coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub enum BrushShape {
+    /// Filled square of side `2 * half_size + 1` centered on `(cx, cy)`
+    Square { cx: i32, cy: i32, half_size: i32 },
+    /// Bresenham line from `(x0, y0)` to `(x1, y1)` with round caps, `thickness` cells wide
+    /// (capped at the grid diagonal)
+    Line { x0: i32, y0: i32, x1: i32, y1: i32, thickness: u32 },
+    /// Hollow circle: cells within `radius` but more than `radius - thickness` from the center
+    Ring { cx: i32, cy: i32, radius: i32, thickness: u32 },
+    /// Filled polygon (even-odd rule, sampled at cell centers); at least three vertices
+    Polygon { points: Vec<(i32, i32)> },
+}
+
+/// Bounding box clipped to the grid, with one bit per cell
+struct Mask {
+    x0: i64,
+    y0: i64,
+    w: usize,
+    h: usize,
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    /// Inclusive bounds, clipped to `width` x `height`
+    fn new(min_x: i64, min_y: i64, max_x: i64, max_y: i64, width: u32, height: u32) -> Self {
+        let x0 = min_x.max(0);
+        let y0 = min_y.max(0);
+        let x1 = max_x.min(width as i64 - 1);
+        let y1 = max_y.min(height as i64 - 1);
+        let w = if x1 >= x0 { (x1 - x0 + 1) as usize } else { 0 };
+        let h = if y1 >= y0 { (y1 - y0 + 1) as usize } else { 0 };
+        Self {
+            x0,
+            y0,
+            w,
+            h,
+            bits: vec![false; w * h],
+        }
+    }
+
+    fn set(&mut self, x: i64, y: i64) {
+        let (lx, ly) = (x - self.x0, y - self.y0);
+        if lx >= 0 && ly >= 0 && (lx as usize) < self.w && (ly as usize) < self.h {
+            self.bits[ly as usize * self.w + lx as usize] = true;
+        }
+    }
+
+    /// Set cells of the bounding box matching `inside(x, y)`
+    fn fill_where(&mut self, inside: impl Fn(i64, i64) -> bool) {
+        for ly in 0..self.h {
+            for lx in 0..self.w {
+                let (x, y) = (self.x0 + lx as i64, self.y0 + ly as i64);
+                if inside(x, y) {
+                    self.bits[ly * self.w + lx] = true;
+                }
+            }
+        }
+    }
+
+    /// Row-major list of set cells
+    fn cells(&self) -> Vec<(u32, u32)> {
+        let mut out = Vec::new();
+        for ly in 0..self.h {
+            for lx in 0..self.w {
+                if self.bits[ly * self.w + lx] {
+                    out.push(((self.x0 + lx as i64) as u32, (self.y0 + ly as i64) as u32));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl BrushShape {
+    /// In-bounds cells covered by the shape on a `width` x `height` grid, row-major
+    pub fn cells(&self, width: u32, height: u32) -> Result<Vec<(u32, u32)>, String> {
+        let mask = match self {
+            Self::Square { cx, cy, half_size } => {
+                let (cx, cy, r) = (*cx as i64, *cy as i64, *half_size as i64);
+                let mut mask = Mask::new(cx - r, cy - r, cx + r, cy + r, width, height);
+                mask.fill_where(|_, _| true);
+                mask
+            }
+            Self::Ring { cx, cy, radius, thickness } => {
+                let (cx, cy, r) = (*cx as i64, *cy as i64, *radius as i64);
+                let inner = r - (*thickness).max(1) as i64;
+                let (outer2, inner2) = (r * r, inner * inner);
+                let mut mask = Mask::new(cx - r, cy - r, cx + r, cy + r, width, height);
+                mask.fill_where(|x, y| {
+                    let d2 = (x - cx) * (x - cx) + (y - cy) * (y - cy);
+                    d2 <= outer2 && (inner < 0 || d2 > inner2)
+                });
+                mask
+            }
+            Self::Line { x0, y0, x1, y1, thickness } => line_mask(*x0, *y0, *x1, *y1, *thickness, width, height),
+            Self::Polygon { points } => polygon_mask(points, width, height)?,
+        };
+        Ok(mask.cells())
+    }
+}
+
+fn line_mask(x0: i32, y0: i32, x1: i32, y1: i32, thickness: u32, width: u32, height: u32) -> Mask {
+    // A pen wider than the grid diagonal covers nothing more
+    let diagonal = (width as f64).hypot(height as f64).ceil();
+    // Round pen: offsets within thickness / 2 of the center
+    let pen_r = (thickness.max(1) as f64).min(diagonal.max(1.0)) / 2.0;
+    let reach = pen_r.floor() as i64;
+    // Half-width of the pen on each row `dy + reach`
+    let pen: Vec<i64> = (-reach..=reach)
+        .map(|dy| {
+            let mut w = (pen_r * pen_r - (dy * dy) as f64).max(0.0).sqrt().floor() as i64;
+            while (((w + 1) * (w + 1) + dy * dy) as f64) <= pen_r * pen_r {
+                w += 1;
+            }
+            while w > 0 && ((w * w + dy * dy) as f64) > pen_r * pen_r {
+                w -= 1;
+            }
+            w
+        })
+        .collect();
+
+    let (x0, y0, x1, y1) = (x0 as i64, y0 as i64, x1 as i64, y1 as i64);
+    let mut mask = Mask::new(
+        x0.min(x1) - reach,
+        y0.min(y1) - reach,
+        x0.max(x1) + reach,
+        y0.max(y1) + reach,
+        width,
+        height,
+    );
+    if mask.w == 0 || mask.h == 0 {
+        return mask;
+    }
+    // Only the part of the segment within `reach` of the grid can paint
+    let bounds = ((-reach, -reach), (width as i64 - 1 + reach, height as i64 - 1 + reach));
+    let Some(((x0, y0), (x1, y1))) = clip_segment((x0, y0), (x1, y1), bounds) else {
+        return mask;
+    };
+
+    // Consecutive pen centers differ by at most one cell, so each row's cells
+    // form one run: track its extent instead of stamping the pen per step.
+    let mut spans = vec![(i64::MAX, i64::MIN); mask.h];
+    let (row0, row1) = (mask.y0, mask.y0 + mask.h as i64 - 1);
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let (mut x, mut y) = (x0, y0);
+    let mut err = dx + dy;
+    loop {
+        for row in (y - reach).max(row0)..=(y + reach).min(row1) {
+            let w = pen[(row - y + reach) as usize];
+            let span = &mut spans[(row - row0) as usize];
+            span.0 = span.0.min(x - w);
+            span.1 = span.1.max(x + w);
+        }
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+    for (i, &(from, to)) in spans.iter().enumerate() {
+        let row = row0 + i as i64;
+        for x in from.max(mask.x0)..=to.min(mask.x0 + mask.w as i64 - 1) {
+            mask.set(x, row);
+        }
+    }
+    mask
+}
+
+/// Clip a segment to an inclusive rectangle (Liang-Barsky); `None` when it misses.
+/// Endpoints already inside are kept exactly.
+fn clip_segment(
+    (x0, y0): (i64, i64),
+    (x1, y1): (i64, i64),
+    ((min_x, min_y), (max_x, max_y)): ((i64, i64), (i64, i64)),
+) -> Option<((i64, i64), (i64, i64))> {
+    let inside = |x: i64, y: i64| (min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y);
+    if inside(x0, y0) && inside(x1, y1) {
+        return Some(((x0, y0), (x1, y1)));
+    }
+    let (dx, dy) = ((x1 - x0) as f64, (y1 - y0) as f64);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    let edges = [
+        (-dx, (x0 - min_x) as f64),
+        (dx, (max_x - x0) as f64),
+        (-dy, (y0 - min_y) as f64),
+        (dy, (max_y - y0) as f64),
+    ];
+    for (p, q) in edges {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    let at = |t: f64| {
+        let x = (x0 as f64 + t * dx).round() as i64;
+        let y = (y0 as f64 + t * dy).round() as i64;
+        (x.clamp(min_x, max_x), y.clamp(min_y, max_y))
+    };
+    let start = if inside(x0, y0) { (x0, y0) } else { at(t0) };
+    let end = if inside(x1, y1) { (x1, y1) } else { at(t1) };
+    Some((start, end))
+}
+
+fn polygon_mask(points: &[(i32, i32)], width: u32, height: u32) -> Result<Mask, String> {
+    if points.len() < 3 {
+        return Err(format!("polygon needs at least 3 vertices, got {}", points.len()));
+    }
+    let min_x = points.iter().map(|p| p.0).min().unwrap_or(0) as i64;
+    let max_x = points.iter().map(|p| p.0).max().unwrap_or(0) as i64;
+    let min_y = points.iter().map(|p| p.1).min().unwrap_or(0) as i64;
+    let max_y = points.iter().map(|p| p.1).max().unwrap_or(0) as i64;
+    let mut mask = Mask::new(min_x, min_y, max_x, max_y, width, height);
+
+    // Scanline at each row's cell centers; fill between pairs of edge crossings
+    let mut crossings: Vec<f64> = Vec::new();
+    for row in 0..mask.h {
+        let y = mask.y0 + row as i64;
+        let sample_y = y as f64 + 0.5;
+        crossings.clear();
+        for i in 0..points.len() {
+            let (ax, ay) = (points[i].0 as f64, points[i].1 as f64);
+            let (bx, by) = {
+                let b = points[(i + 1) % points.len()];
+                (b.0 as f64, b.1 as f64)
+            };
+            if (ay <= sample_y && sample_y < by) || (by <= sample_y && sample_y < ay) {
+                crossings.push(ax + (sample_y - ay) * (bx - ax) / (by - ay));
+            }
+        }
+        crossings.sort_by(f64::total_cmp);
+        for pair in crossings.chunks_exact(2) {
+            // Cells whose center x + 0.5 lies in [pair[0], pair[1])
+            let start = ((pair[0] - 0.5).ceil() as i64).max(mask.x0);
+            let end = ((pair[1] - 0.5).ceil() as i64).min(mask.x0 + mask.w as i64);
+            for x in start..end {
+                mask.set(x, y);
+            }
+        }
+    }
+    Ok(mask)
+}
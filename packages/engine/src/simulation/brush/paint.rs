@@ -0,0 +1,46 @@
+use crate::elements::EL_EMPTY;
+
+use super::super::commands;
+use super::super::WorldCore;
+use super::{Brush, BrushMode, BrushShape};
+
+/// Paint `shape` with `brush` (user edit: recorded in undo history).
+/// Returns the number of cells that changed.
+pub(crate) fn paint(world: &mut WorldCore, shape: &BrushShape, brush: &Brush) -> Result<u32, String> {
+    if !brush.is_eraser() && !world.content.is_valid_element_id(brush.element) {
+        return Err(format!("invalid brush element id {}", brush.element));
+    }
+    let cells = shape.cells(world.grid.width(), world.grid.height())?;
+
+    let mut changed = 0u32;
+    for (x, y) in cells {
+        if paint_cell(world, x, y, brush) {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+fn paint_cell(world: &mut WorldCore, x: u32, y: u32, brush: &Brush) -> bool {
+    let current = world.grid.types[world.grid.index(x, y)];
+    let selected = match brush.mode {
+        BrushMode::FillEmpty => current == EL_EMPTY,
+        BrushMode::ReplaceAll => true,
+        BrushMode::ReplaceElement(id) => current == id,
+        BrushMode::ReplaceCategory(category) => {
+            current != EL_EMPTY && world.content.props(current).is_some_and(|p| p.category == category)
+        }
+    };
+    // Repainting a cell with its own element would only reset it
+    if !selected || current == brush.element {
+        return false;
+    }
+
+    if current != EL_EMPTY {
+        commands::remove_particle(world, x, y);
+    }
+    if brush.is_eraser() {
+        return true;
+    }
+    commands::add_particle(world, x, y, brush.element)
+}
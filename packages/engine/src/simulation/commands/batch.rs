@@ -0,0 +1,114 @@
+//! Batched edit commands: many brush dabs per wasm call.
+//!
+//! A batch is a sequence of commands in the recording wire format
+//! (`replay/command.rs`: one tag byte, then little-endian fields), so the
+//! tags are stable. Only editing commands are accepted:
+//! add/remove (single and radius), brushes, flood fill, heat, velocity tools
+//! and stroke begin/end.
+//!
+//! Each command produces one `i32` result:
+//! - add/remove particle: 1 if the cell changed, else 0
+//! - paint, flood fill, velocity tools: number of cells changed, or -1 on error
+//! - everything else: 0
+
+use super::replay::command::RecordedCommand;
+use super::snapshot::codec::ByteReader;
+use super::{Brush, BrushMode, WorldCore};
+
+/// Result code for a command that reported an error
+const BATCH_ERROR: i32 = -1;
+
+/// Decode the whole batch first so a malformed buffer runs nothing
+fn decode_batch(bytes: &[u8]) -> Result<Vec<RecordedCommand>, String> {
+    let mut r = ByteReader::new(bytes);
+    let mut cmds = Vec::new();
+    while r.remaining() > 0 {
+        let index = cmds.len();
+        let cmd = RecordedCommand::decode(&mut r).map_err(|e| format!("command {}: {}", index, e))?;
+        if !is_batchable(&cmd) {
+            return Err(format!("command {}: not allowed in a batch", index));
+        }
+        cmds.push(cmd);
+    }
+    Ok(cmds)
+}
+
+fn is_batchable(cmd: &RecordedCommand) -> bool {
+    matches!(
+        cmd,
+        RecordedCommand::AddParticle { .. }
+            | RecordedCommand::AddParticlesInRadius { .. }
+            | RecordedCommand::RemoveParticle { .. }
+            | RecordedCommand::RemoveParticlesInRadius { .. }
+            | RecordedCommand::Paint { .. }
+            | RecordedCommand::FloodFill { .. }
+            | RecordedCommand::ApplyHeatInRadius { .. }
+            | RecordedCommand::SetTemperatureInRadius { .. }
+            | RecordedCommand::ApplyImpulse { .. }
+            | RecordedCommand::ApplyBlast { .. }
+            | RecordedCommand::ApplyVortex { .. }
+            | RecordedCommand::BeginStroke
+            | RecordedCommand::EndStroke
+    )
+}
+
+fn count_or_error(result: Result<u32, String>) -> i32 {
+    result.map_or(BATCH_ERROR, |n| n.min(i32::MAX as u32) as i32)
+}
+
+/// Run every command in order through the public `WorldCore` API
+/// (so undo history and input recording see them as individual calls).
+pub(super) fn apply_commands(world: &mut WorldCore, bytes: &[u8]) -> Result<Vec<i32>, String> {
+    let cmds = decode_batch(bytes)?;
+    let mut results = Vec::with_capacity(cmds.len());
+    for cmd in cmds {
+        let result = match cmd {
+            RecordedCommand::AddParticle { x, y, element } => world.add_particle(x, y, element) as i32,
+            RecordedCommand::AddParticlesInRadius { cx, cy, radius, element } => {
+                world.add_particles_in_radius(cx, cy, radius, element);
+                0
+            }
+            RecordedCommand::RemoveParticle { x, y } => world.remove_particle(x, y) as i32,
+            RecordedCommand::RemoveParticlesInRadius { cx, cy, radius } => {
+                world.remove_particles_in_radius(cx, cy, radius);
+                0
+            }
+            RecordedCommand::Paint { shape, element, mode, target } => match BrushMode::from_code(mode, target) {
+                Some(mode) => count_or_error(world.paint(&shape, &Brush::with_mode(element, mode))),
+                None => BATCH_ERROR,
+            },
+            RecordedCommand::FloodFill { x, y, element, connectivity, max_cells } => {
+                count_or_error(world.flood_fill(x, y, element, connectivity, max_cells))
+            }
+            RecordedCommand::ApplyHeatInRadius { cx, cy, radius, delta, falloff } => {
+                world.apply_heat_in_radius(cx, cy, radius, delta, falloff);
+                0
+            }
+            RecordedCommand::SetTemperatureInRadius { cx, cy, radius, temp } => {
+                world.set_temperature_in_radius(cx, cy, radius, temp);
+                0
+            }
+            RecordedCommand::ApplyImpulse { cx, cy, radius, vx, vy } => {
+                count_or_error(Ok(world.apply_impulse_in_radius(cx, cy, radius, vx, vy)))
+            }
+            RecordedCommand::ApplyBlast { cx, cy, radius, strength } => {
+                count_or_error(Ok(world.apply_blast(cx, cy, radius, strength)))
+            }
+            RecordedCommand::ApplyVortex { cx, cy, radius, strength } => {
+                count_or_error(Ok(world.apply_vortex(cx, cy, radius, strength)))
+            }
+            RecordedCommand::BeginStroke => {
+                world.begin_stroke();
+                0
+            }
+            RecordedCommand::EndStroke => {
+                world.end_stroke();
+                0
+            }
+            // Filtered out by `decode_batch`
+            _ => BATCH_ERROR,
+        };
+        results.push(result);
+    }
+    Ok(results)
+}
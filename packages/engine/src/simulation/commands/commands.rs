@@ -1,8 +1,17 @@
-use crate::elements::EL_EMPTY;
+use crate::elements::{ElementId, EL_EMPTY};
 
 use super::WorldCore;
 
-pub(super) fn add_particle(world: &mut WorldCore, x: u32, y: u32, element: u8) -> bool {
+/// User edit: recorded in the undo history
+pub(super) fn add_particle(world: &mut WorldCore, x: u32, y: u32, element: ElementId) -> bool {
+    if x < world.grid.width() && y < world.grid.height() {
+        world.history.record_cell(&world.grid, x, y);
+    }
+    spawn_particle(world, x, y, element)
+}
+
+/// Simulation-internal placement (reactions): not recorded
+pub(super) fn spawn_particle(world: &mut WorldCore, x: u32, y: u32, element: ElementId) -> bool {
     if x >= world.grid.width() || y >= world.grid.height() {
         return false;
     }
@@ -40,7 +49,7 @@ pub(super) fn add_particle(world: &mut WorldCore, x: u32, y: u32, element: u8) -
     true
 }
 
-pub(super) fn add_particles_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, element: u8) {
+pub(super) fn add_particles_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, element: ElementId) {
     let r2 = radius * radius;
     for dy in -radius..=radius {
         for dx in -radius..=radius {
@@ -55,7 +64,16 @@ pub(super) fn add_particles_in_radius(world: &mut WorldCore, cx: i32, cy: i32, r
     }
 }
 
+/// User edit: recorded in the undo history
 pub(super) fn remove_particle(world: &mut WorldCore, x: u32, y: u32) -> bool {
+    if x < world.grid.width() && y < world.grid.height() {
+        world.history.record_cell(&world.grid, x, y);
+    }
+    despawn_particle(world, x, y)
+}
+
+/// Simulation-internal removal (reactions): not recorded
+pub(super) fn despawn_particle(world: &mut WorldCore, x: u32, y: u32) -> bool {
     if x >= world.grid.width() || y >= world.grid.height() {
         return false;
     }
@@ -87,8 +105,22 @@ pub(super) fn remove_particles_in_radius(world: &mut WorldCore, cx: i32, cy: i32
 }
 
 pub(super) fn clear(world: &mut WorldCore) {
+    for idx in 0..world.grid.size() {
+        if world.grid.types[idx] != EL_EMPTY {
+            let (x, y) = world.grid.coords(idx);
+            world.history.record_cell(&world.grid, x, y);
+        }
+    }
+    let bodies = std::mem::take(&mut world.rigid_bodies);
+    world.history.record_bodies(bodies);
+    reset(world);
+}
+
+/// Empty the grid and drop rigid bodies without recording undo
+/// (for callers that clear the history right after)
+pub(super) fn reset(world: &mut WorldCore) {
+    world.rigid_bodies = Default::default();
     world.grid.clear();
-    world.rigid_bodies = super::RigidBodySystem::new();
     world.particle_count = 0;
     world.frame = 0;
 }
@@ -0,0 +1,114 @@
+use crate::elements::{ElementId, EL_EMPTY};
+
+use super::commands;
+use super::WorldCore;
+
+/// Inclusive horizontal run of cells on one row
+struct Span {
+    y: u32,
+    x0: u32,
+    x1: u32,
+}
+
+/// Replace the connected region of the element at `(x, y)` (or of empty space) with `element`.
+///
+/// `connectivity` is 4 (edges) or 8 (edges and corners). The region is collected span by
+/// span first; if it has more than `max_cells` cells nothing is changed and an error is
+/// returned. Filling with `EL_EMPTY` erases the region. Returns the number of cells changed.
+pub(super) fn flood_fill(
+    world: &mut WorldCore,
+    x: u32,
+    y: u32,
+    element: ElementId,
+    connectivity: u8,
+    max_cells: u32,
+) -> Result<u32, String> {
+    if connectivity != 4 && connectivity != 8 {
+        return Err(format!("connectivity must be 4 or 8, got {}", connectivity));
+    }
+    if element != EL_EMPTY && !world.content.is_valid_element_id(element) {
+        return Err(format!("invalid fill element id {}", element));
+    }
+    if x >= world.grid.width() || y >= world.grid.height() {
+        return Ok(0);
+    }
+    let target = world.grid.types[world.grid.index(x, y)];
+    if target == element {
+        return Ok(0);
+    }
+
+    let spans = collect_spans(world, x, y, connectivity == 8, max_cells)?;
+
+    let mut changed = 0u32;
+    for span in spans {
+        for sx in span.x0..=span.x1 {
+            if target != EL_EMPTY {
+                commands::remove_particle(world, sx, span.y);
+            }
+            if element == EL_EMPTY || commands::add_particle(world, sx, span.y, element) {
+                changed += 1;
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Scanline flood: each popped seed grows into a full span, then the rows above and
+/// below are scanned once over the span's extent for new seeds.
+fn collect_spans(world: &WorldCore, x: u32, y: u32, diagonal: bool, max_cells: u32) -> Result<Vec<Span>, String> {
+    let grid = &world.grid;
+    let (w, h) = (grid.width(), grid.height());
+    let target = grid.types[grid.index(x, y)];
+    let mut visited = vec![false; grid.size()];
+    let matches = |visited: &[bool], x: u32, y: u32| {
+        let idx = grid.index(x, y);
+        !visited[idx] && grid.types[idx] == target
+    };
+
+    let mut spans = Vec::new();
+    let mut total = 0u64;
+    let mut seeds = vec![(x, y)];
+    while let Some((sx, sy)) = seeds.pop() {
+        if !matches(&visited, sx, sy) {
+            continue;
+        }
+        let mut x0 = sx;
+        while x0 > 0 && matches(&visited, x0 - 1, sy) {
+            x0 -= 1;
+        }
+        let mut x1 = sx;
+        while x1 + 1 < w && matches(&visited, x1 + 1, sy) {
+            x1 += 1;
+        }
+        for cx in x0..=x1 {
+            visited[grid.index(cx, sy)] = true;
+        }
+        total += (x1 - x0 + 1) as u64;
+        if total > max_cells as u64 {
+            return Err(format!("fill region exceeds {} cells", max_cells));
+        }
+        spans.push(Span { y: sy, x0, x1 });
+
+        // Diagonal neighbours reach one cell past each end of the span
+        let (scan0, scan1) = if diagonal {
+            (x0.saturating_sub(1), (x1 + 1).min(w - 1))
+        } else {
+            (x0, x1)
+        };
+        let rows = [sy.checked_sub(1), Some(sy + 1).filter(|&ny| ny < h)];
+        for ny in rows.into_iter().flatten() {
+            let mut in_run = false;
+            for nx in scan0..=scan1 {
+                if matches(&visited, nx, ny) {
+                    if !in_run {
+                        seeds.push((nx, ny));
+                        in_run = true;
+                    }
+                } else {
+                    in_run = false;
+                }
+            }
+        }
+    }
+    Ok(spans)
+}
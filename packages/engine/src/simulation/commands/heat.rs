@@ -0,0 +1,50 @@
+use super::WorldCore;
+
+/// Nothing gets colder than absolute zero
+pub(super) const ABSOLUTE_ZERO: f32 = -273.15;
+
+/// Visit in-bounds cells within `radius` of `(cx, cy)` with their distance to the center
+fn for_each_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, mut f: impl FnMut(&mut WorldCore, u32, u32, f32)) {
+    let r2 = radius * radius;
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy > r2 {
+                continue;
+            }
+            let (x, y) = (cx + dx, cy + dy);
+            if !world.grid.in_bounds(x, y) {
+                continue;
+            }
+            let dist = ((dx * dx + dy * dy) as f32).sqrt();
+            f(world, x as u32, y as u32, dist);
+        }
+    }
+}
+
+/// Add `delta` degrees to particles and air alike (user edit: recorded in undo history).
+/// With `falloff`, the change fades linearly from the center to the rim.
+/// Phase changes happen on the next step's temperature pass.
+pub(super) fn apply_heat_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, delta: f32, falloff: bool) {
+    if !delta.is_finite() {
+        return;
+    }
+    let span = (radius + 1) as f32;
+    for_each_in_radius(world, cx, cy, radius, |world, x, y, dist| {
+        let scale = if falloff { 1.0 - dist / span } else { 1.0 };
+        world.history.record_cell(&world.grid, x, y);
+        let t = world.grid.get_temp(x as i32, y as i32) + delta * scale;
+        world.grid.set_temp(x, y, t.max(ABSOLUTE_ZERO));
+    });
+}
+
+/// Set particles and air to exactly `temp` (user edit: recorded in undo history)
+pub(super) fn set_temperature_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, temp: f32) {
+    if !temp.is_finite() {
+        return;
+    }
+    let temp = temp.max(ABSOLUTE_ZERO);
+    for_each_in_radius(world, cx, cy, radius, |world, x, y, _| {
+        world.history.record_cell(&world.grid, x, y);
+        world.grid.set_temp(x, y, temp);
+    });
+}
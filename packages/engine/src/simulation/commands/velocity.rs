@@ -0,0 +1,86 @@
+use crate::elements::{EL_EMPTY, MAX_VELOCITY};
+use crate::physics::is_velocity_driven;
+
+use super::WorldCore;
+
+/// Visit particles within `radius` that the physics pass moves by velocity.
+/// `f` gets the offset from the center and a linear falloff weight (1 at center, >0 at rim)
+/// and returns the velocity to add.
+fn push_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, f: impl Fn(f32, f32, f32) -> (f32, f32)) -> u32 {
+    let r2 = radius * radius;
+    let span = (radius + 1) as f32;
+    let mut pushed = 0u32;
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy > r2 {
+                continue;
+            }
+            let (x, y) = (cx + dx, cy + dy);
+            if !world.grid.in_bounds(x, y) {
+                continue;
+            }
+            let (x, y) = (x as u32, y as u32);
+            let idx = world.grid.index(x, y);
+            let element = world.grid.types[idx];
+            if element == EL_EMPTY {
+                continue;
+            }
+            match world.content.props(element) {
+                Some(props) if is_velocity_driven(props) => {}
+                _ => continue,
+            }
+
+            let (dxf, dyf) = (dx as f32, dy as f32);
+            let weight = 1.0 - (dxf * dxf + dyf * dyf).sqrt() / span;
+            let (ax, ay) = f(dxf, dyf, weight);
+            if ax == 0.0 && ay == 0.0 {
+                continue;
+            }
+            world.history.record_cell(&world.grid, x, y);
+            world.grid.vx[idx] = (world.grid.vx[idx] + ax).clamp(-MAX_VELOCITY, MAX_VELOCITY);
+            world.grid.vy[idx] = (world.grid.vy[idx] + ay).clamp(-MAX_VELOCITY, MAX_VELOCITY);
+            pushed += 1;
+        }
+    }
+    pushed
+}
+
+/// Add `(vx, vy)` to every particle in radius (throw / blower tool)
+pub(super) fn apply_impulse_in_radius(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, vx: f32, vy: f32) -> u32 {
+    if !vx.is_finite() || !vy.is_finite() {
+        return 0;
+    }
+    push_in_radius(world, cx, cy, radius, |_, _, _| (vx, vy))
+}
+
+/// Push particles away from the center, strongest near it (explosion tool).
+/// The center cell itself has no direction and is left alone.
+pub(super) fn apply_blast(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, strength: f32) -> u32 {
+    if !strength.is_finite() {
+        return 0;
+    }
+    push_in_radius(world, cx, cy, radius, |dx, dy, weight| {
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist == 0.0 {
+            return (0.0, 0.0);
+        }
+        let s = strength * weight / dist;
+        (dx * s, dy * s)
+    })
+}
+
+/// Swirl particles around the center; positive `strength` turns clockwise on screen
+pub(super) fn apply_vortex(world: &mut WorldCore, cx: i32, cy: i32, radius: i32, strength: f32) -> u32 {
+    if !strength.is_finite() {
+        return 0;
+    }
+    push_in_radius(world, cx, cy, radius, |dx, dy, weight| {
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist == 0.0 {
+            return (0.0, 0.0);
+        }
+        // (dx, dy) turned 90° clockwise with y pointing down
+        let s = strength * weight / dist;
+        (-dy * s, dx * s)
+    })
+}
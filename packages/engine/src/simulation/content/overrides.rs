@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use crate::domain::content::{ContentRegistry, ElementProperty};
+use crate::elements::{ElementId, EL_EMPTY};
+
+use super::WorldCore;
+
+/// Tune one element property on the live registry (ModStudio live preview).
+///
+/// The shared registry is cloned on first write; the registry as loaded is kept
+/// so `reset_element_properties` can restore it. Loading or hot-reloading a
+/// bundle drops all overrides.
+pub(super) fn set_element_property(world: &mut WorldCore, id: ElementId, field: &str, value: f64) -> Result<(), String> {
+    let property = ElementProperty::parse(field, value)?;
+    if !world.content.is_valid_element_id(id) {
+        return Err(format!("element id out of range: {}", id));
+    }
+
+    if world.content_base.is_none() {
+        world.content_base = Some(Arc::clone(&world.content));
+    }
+    Arc::make_mut(&mut world.content).set_element_property(id, property)?;
+
+    if let ElementProperty::Color(_) = property {
+        recolor(world, |el| el == id);
+    }
+    Ok(())
+}
+
+/// Drop every override and go back to the registry as loaded
+pub(super) fn reset_element_properties(world: &mut WorldCore) {
+    let Some(base) = world.content_base.take() else {
+        return;
+    };
+    let tuned = Arc::clone(&world.content);
+    world.content = base;
+    let content = Arc::clone(&world.content);
+    recolor(world, |el| color_of(&tuned, el) != color_of(&content, el));
+}
+
+fn color_of(content: &ContentRegistry, id: ElementId) -> Option<u32> {
+    content.props(id).map(|p| p.color)
+}
+
+/// Repaint cells of the matching elements from the current palette
+fn recolor(world: &mut WorldCore, matches: impl Fn(ElementId) -> bool) {
+    let frame = world.frame as u32;
+    let grid = &mut world.grid;
+    for idx in 0..grid.size() {
+        let element = grid.types[idx];
+        if element == EL_EMPTY || !matches(element) {
+            continue;
+        }
+        let (x, y) = grid.coords(idx);
+        let seed = ((x * 7 + y * 13 + frame) & 31) as u8;
+        if let Some(color) = world.content.color_with_variation(element, seed) {
+            grid.colors[idx] = color;
+        }
+    }
+}
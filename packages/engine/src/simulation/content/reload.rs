@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::domain::content::ContentRegistry;
+use crate::elements::{ElementId, EL_EMPTY};
+
+use super::snapshot::remap::ElementRemap;
+use super::WorldCore;
+
+/// Swap the content registry while keeping the scene.
+///
+/// Particles are migrated by element key and recolored from the new palette.
+/// Elements missing from the new bundle become `fallback_key` (or empty when `None`).
+/// Returns the keys that disappeared from the registry (plus any element whose
+/// cells had to be cleared because they could not be migrated).
+pub(super) fn reload_content_bundle_json(
+    world: &mut WorldCore,
+    json: &str,
+    fallback_key: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let registry = ContentRegistry::from_bundle_json(json)?;
+
+    let fallback = match fallback_key {
+        None => None,
+        Some(key) => match registry.id_by_key(key) {
+            Some(EL_EMPTY) => None,
+            Some(id) => Some(id),
+            None => return Err(format!("fallback element not found in bundle: {}", key)),
+        },
+    };
+
+    let old = Arc::clone(&world.content);
+    let mut remap = ElementRemap::from_key_table(&old.element_key_table(), &registry);
+    if let Some(id) = fallback {
+        remap = remap.with_fallback(id);
+    }
+
+    let mut disappeared: Vec<String> = (0..old.element_count())
+        .filter_map(|idx| old.key_by_id(idx as ElementId))
+        .filter(|key| registry.id_by_key(key).is_none())
+        .map(|key| key.to_string())
+        .collect();
+
+    let frame = world.frame as u32;
+    let grid = &mut world.grid;
+    let mut cleared = 0u32;
+    for idx in 0..grid.size() {
+        let prev = grid.types[idx];
+        if prev == EL_EMPTY {
+            continue;
+        }
+        let (x, y) = grid.coords(idx);
+        let seed = ((x * 7 + y * 13 + frame) & 31) as u8;
+        let migrated = remap
+            .map(prev)
+            .and_then(|id| registry.color_with_variation(id, seed).map(|color| (id, color)));
+        match migrated {
+            Some((id, color)) => {
+                grid.types[idx] = id;
+                grid.colors[idx] = color;
+            }
+            None => {
+                // Unmapped, or mapped to an id the new palette cannot color:
+                // never leave a cell of the old registry behind
+                if let Some(key) = old.key_by_id(prev) {
+                    if !disappeared.iter().any(|k| k == key) {
+                        disappeared.push(key.to_string());
+                    }
+                }
+                grid.clear_cell(x, y);
+                cleared += 1;
+            }
+        }
+    }
+
+    world.rigid_bodies.remap_elements(|el| remap.map(el));
+
+    world.particle_count = world.particle_count.saturating_sub(cleared);
+    world.content = Arc::new(registry);
+    world.content_base = None;
+    Ok(disappeared)
+}
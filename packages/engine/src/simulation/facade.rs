@@ -1,6 +1,9 @@
 use wasm_bindgen::prelude::*;
 
+use crate::elements::ElementId;
+
 use super::perf_stats::PerfStats;
+use super::{Brush, BrushShape, CellInfo, ImageImportOptions, RegionStats, RenderView, ResizeAnchor, StampBlend, TerrainConfig};
 use super::WorldCore;
 
 #[wasm_bindgen]
@@ -8,6 +11,7 @@ pub struct AbiLayout {
     types_ptr: u32,
     types_len_elements: u32,
     types_len_bytes: u32,
+    types_element_bytes: u32,
     colors_ptr: u32,
     colors_len_elements: u32,
     colors_len_bytes: u32,
@@ -24,6 +28,9 @@ impl AbiLayout {
     pub fn types_len_elements(&self) -> u32 { self.types_len_elements }
     #[wasm_bindgen(getter)]
     pub fn types_len_bytes(&self) -> u32 { self.types_len_bytes }
+    /// 1 (`Uint8Array`) or 2 (`Uint16Array`, `wide-element-ids` builds)
+    #[wasm_bindgen(getter)]
+    pub fn types_element_bytes(&self) -> u32 { self.types_element_bytes }
 
     #[wasm_bindgen(getter)]
     pub fn colors_ptr(&self) -> u32 { self.colors_ptr }
@@ -43,6 +50,8 @@ impl AbiLayout {
 #[wasm_bindgen]
 pub struct World {
     core: WorldCore,
+    /// Shared buffer JS fills with packed commands for `apply_commands`
+    command_buffer: Vec<u8>,
 }
 
 #[wasm_bindgen]
@@ -52,6 +61,7 @@ impl World {
     pub fn new(width: u32, height: u32) -> Self {
         Self {
             core: WorldCore::new(width, height),
+            command_buffer: Vec::new(),
         }
     }
 
@@ -93,21 +103,61 @@ impl World {
         self.core.set_ambient_temperature(temp);
     }
 
+    /// Reseed the simulation RNG streams for reproducible scenes
+    pub fn set_seed(&mut self, seed: u64) {
+        self.core.set_seed(seed);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn seed(&self) -> u64 { self.core.seed() }
+
     /// DEBUG: Get current ambient temperature
     pub fn get_ambient_temperature(&self) -> f32 {
         self.core.get_ambient_temperature()
     }
 
     /// Add a particle at position
-    pub fn add_particle(&mut self, x: u32, y: u32, element: u8) -> bool {
+    pub fn add_particle(&mut self, x: u32, y: u32, element: ElementId) -> bool {
         self.core.add_particle(x, y, element)
     }
 
     /// Add particles in radius (brush)
-    pub fn add_particles_in_radius(&mut self, cx: i32, cy: i32, radius: i32, element: u8) {
+    pub fn add_particles_in_radius(&mut self, cx: i32, cy: i32, radius: i32, element: ElementId) {
         self.core.add_particles_in_radius(cx, cy, radius, element)
     }
 
+    /// Filled square brush of side `2 * half_size + 1`; returns the number of cells changed
+    pub fn paint_square(&mut self, cx: i32, cy: i32, half_size: i32, brush: &Brush) -> Result<u32, JsValue> {
+        self.core
+            .paint(&BrushShape::Square { cx, cy, half_size }, brush)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Straight line with round caps, `thickness` cells wide
+    pub fn paint_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, thickness: u32, brush: &Brush) -> Result<u32, JsValue> {
+        self.core
+            .paint(&BrushShape::Line { x0, y0, x1, y1, thickness }, brush)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Hollow circle `thickness` cells wide, measured inward from `radius`
+    pub fn paint_ring(&mut self, cx: i32, cy: i32, radius: i32, thickness: u32, brush: &Brush) -> Result<u32, JsValue> {
+        self.core
+            .paint(&BrushShape::Ring { cx, cy, radius, thickness }, brush)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Filled polygon from flat `[x0, y0, x1, y1, ...]` vertices
+    pub fn paint_polygon(&mut self, points: &[i32], brush: &Brush) -> Result<u32, JsValue> {
+        if !points.len().is_multiple_of(2) {
+            return Err(JsValue::from_str("polygon points must be x, y pairs"));
+        }
+        let points = points.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+        self.core
+            .paint(&BrushShape::Polygon { points }, brush)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     /// Remove particle at position
     pub fn remove_particle(&mut self, x: u32, y: u32) -> bool {
         self.core.remove_particle(x, y)
@@ -118,11 +168,188 @@ impl World {
         self.core.remove_particles_in_radius(cx, cy, radius)
     }
 
+    /// Heat (positive delta) or cool (negative) particles and air in radius
+    pub fn apply_heat_in_radius(&mut self, cx: i32, cy: i32, radius: i32, delta: f32, falloff: bool) {
+        self.core.apply_heat_in_radius(cx, cy, radius, delta, falloff)
+    }
+
+    /// Set particles and air in radius to a fixed temperature
+    pub fn set_temperature_in_radius(&mut self, cx: i32, cy: i32, radius: i32, temp: f32) {
+        self.core.set_temperature_in_radius(cx, cy, radius, temp)
+    }
+
+    /// Add velocity to movable particles in radius; returns the number pushed
+    pub fn apply_impulse_in_radius(&mut self, cx: i32, cy: i32, radius: i32, vx: f32, vy: f32) -> u32 {
+        self.core.apply_impulse_in_radius(cx, cy, radius, vx, vy)
+    }
+
+    /// Explosion: push particles away from (cx, cy)
+    pub fn apply_blast(&mut self, cx: i32, cy: i32, radius: i32, strength: f32) -> u32 {
+        self.core.apply_blast(cx, cy, radius, strength)
+    }
+
+    /// Swirl particles around (cx, cy); negative strength turns counter-clockwise
+    pub fn apply_vortex(&mut self, cx: i32, cy: i32, radius: i32, strength: f32) -> u32 {
+        self.core.apply_vortex(cx, cy, radius, strength)
+    }
+
+    /// Fill tool: replace the connected region under (x, y); `connectivity` is 4 or 8
+    /// Returns the number of cells changed
+    pub fn flood_fill(&mut self, x: u32, y: u32, element: ElementId, connectivity: u8, max_cells: u32) -> Result<u32, JsValue> {
+        self.core
+            .flood_fill(x, y, element, connectivity, max_cells)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Fill the world with seeded terrain; returns the number of particles placed
+    pub fn generate_terrain(&mut self, config: &TerrainConfig) -> Result<u32, JsValue> {
+        self.core.generate_terrain(config).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Make the command buffer at least `bytes` long and return its address.
+    /// May grow wasm memory: re-create JS views after calling this
+    pub fn command_buffer_ptr(&mut self, bytes: usize) -> *mut u8 {
+        if self.command_buffer.len() < bytes {
+            self.command_buffer.resize(bytes, 0);
+        }
+        self.command_buffer.as_mut_ptr()
+    }
+
+    /// Execute the first `len` bytes of the command buffer; one result per command
+    pub fn apply_commands(&mut self, len: usize) -> Result<Vec<i32>, JsValue> {
+        let Some(bytes) = self.command_buffer.get(..len) else {
+            return Err(JsValue::from_str(&format!(
+                "command batch of {} bytes exceeds the {} byte buffer",
+                len,
+                self.command_buffer.len()
+            )));
+        };
+        self.core.apply_commands(bytes).map_err(|e| JsValue::from_str(&e))
+    }
+
     /// Clear all particles
     pub fn clear(&mut self) {
         self.core.clear();
     }
 
+    /// Copy a region into an encoded stamp
+    pub fn copy_region(&self, x: i32, y: i32, w: u32, h: u32) -> Result<Vec<u8>, JsValue> {
+        let stamp = self.core.copy_region(x, y, w, h).map_err(|e| JsValue::from_str(&e))?;
+        Ok(self.core.encode_stamp(&stamp))
+    }
+
+    /// Cut a region into an encoded stamp
+    pub fn cut_region(&mut self, x: i32, y: i32, w: u32, h: u32) -> Result<Vec<u8>, JsValue> {
+        let stamp = self.core.cut_region(x, y, w, h).map_err(|e| JsValue::from_str(&e))?;
+        Ok(self.core.encode_stamp(&stamp))
+    }
+
+    /// Paste an encoded stamp; `mode`: 0 = overwrite, 1 = into-empty, 2 = skip-empty-source
+    /// Returns the number of particles placed
+    pub fn paste_stamp(&mut self, bytes: &[u8], x: i32, y: i32, quarter_turns: u8, mirror: bool, mode: u8) -> Result<u32, JsValue> {
+        let mode = StampBlend::from_code(mode)
+            .ok_or_else(|| JsValue::from_str(&format!("invalid stamp blend mode {}", mode)))?;
+        let stamp = self.core.decode_stamp(bytes).map_err(|e| JsValue::from_str(&e))?;
+        Ok(self.core.paste_stamp(&stamp, x, y, quarter_turns, mirror, mode))
+    }
+
+    /// Import an RGBA buffer (e.g. `ImageData.data`) as particles; returns the number placed
+    pub fn import_image(&mut self, rgba: &[u8], width: u32, height: u32, x: i32, y: i32, options: &ImageImportOptions) -> Result<u32, JsValue> {
+        self.core
+            .import_image(rgba, width, height, x, y, options)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Group following edits into one undo step (pointer down)
+    pub fn begin_stroke(&mut self) {
+        self.core.begin_stroke();
+    }
+
+    /// Close the current undo step (pointer up)
+    pub fn end_stroke(&mut self) {
+        self.core.end_stroke();
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.core.undo()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.core.redo()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.core.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.core.can_redo()
+    }
+
+    /// Memory budget for undo/redo records in bytes
+    pub fn set_history_budget(&mut self, bytes: usize) {
+        self.core.set_history_budget(bytes);
+    }
+
+    pub fn clear_history(&mut self) {
+        self.core.clear_history();
+    }
+
+    /// Serialize the whole world into a versioned binary snapshot
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        self.core.save_snapshot()
+    }
+
+    /// Restore a world saved with `save_snapshot`
+    /// Returns the keys of saved elements missing from the current content (their cells are emptied).
+    /// Grid pointers may change (world size comes from the snapshot) - re-read `abi_layout`
+    pub fn load_snapshot(&mut self, bytes: &[u8]) -> Result<Vec<String>, JsValue> {
+        let report = self
+            .core
+            .load_snapshot(bytes)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(report.missing_elements)
+    }
+
+    /// Load a JSON scene; returns one rigid body id per scene body (0 = could not be placed)
+    pub fn load_scene(&mut self, json: &str) -> Result<Vec<u32>, JsValue> {
+        let report = self.core.load_scene(json).map_err(|e| JsValue::from_str(&e))?;
+        Ok(report.rigid_bodies)
+    }
+
+    /// Hash of grid, rigid bodies and RNG state (desync/regression checks)
+    pub fn state_hash(&self) -> u64 {
+        self.core.state_hash()
+    }
+
+    /// Per-chunk grid hashes, row-major - compare to find where two worlds diverge
+    pub fn chunk_hashes(&self) -> Vec<u64> {
+        self.core.chunk_hashes()
+    }
+
+    /// Start logging every mutating call for a shareable repro file
+    pub fn start_recording(&mut self) {
+        self.core.start_recording();
+    }
+
+    /// Stop logging and return the recording bytes (undefined if not recording)
+    pub fn stop_recording(&mut self) -> Option<Vec<u8>> {
+        self.core.stop_recording()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.core.is_recording()
+    }
+
+    /// Re-run a recording and verify the final state; throws on mismatch
+    /// Load the same content bundle first. Grid pointers may change - re-read `abi_layout`
+    pub fn replay_recording(&mut self, bytes: &[u8]) -> Result<(), JsValue> {
+        self.core
+            .replay_recording(bytes)
+            .map_err(|e| JsValue::from_str(&e))?;
+        Ok(())
+    }
+
     pub fn load_content_bundle(&mut self, json: String) -> Result<(), JsValue> {
         self.core
             .load_content_bundle_json(&json)
@@ -130,21 +357,51 @@ impl World {
         Ok(())
     }
 
+    /// Hot-reload a content bundle, keeping the scene (ModStudio live preview)
+    /// Returns the element keys that disappeared
+    pub fn reload_content_bundle(&mut self, json: String, fallback_key: Option<String>) -> Result<Vec<String>, JsValue> {
+        self.core
+            .reload_content_bundle_json(&json, fallback_key.as_deref())
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     pub fn get_content_manifest_json(&self) -> String {
         self.core.get_content_manifest_json()
     }
 
+    /// Tune one element property live (ModStudio); see `reset_element_properties`
+    pub fn set_element_property(&mut self, element: ElementId, field: &str, value: f64) -> Result<(), JsValue> {
+        self.core
+            .set_element_property(element, field, value)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Restore every element property to the loaded bundle's values
+    pub fn reset_element_properties(&mut self) {
+        self.core.reset_element_properties();
+    }
+
+    /// Resize the world keeping the scene; `anchor` is 0..=8 row-major from top-left (4 = center)
+    /// Grid pointers change - re-read `abi_layout`
+    pub fn resize(&mut self, width: u32, height: u32, anchor: u8) -> Result<(), JsValue> {
+        let anchor = ResizeAnchor::from_code(anchor)
+            .ok_or_else(|| JsValue::from_str(&format!("invalid resize anchor {}", anchor)))?;
+        self.core
+            .resize(width, height, anchor)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     // === RIGID BODY API ===
 
     /// Spawn a rectangular rigid body at position (x, y) with size (w, h)
     /// Returns the body ID
-    pub fn spawn_rigid_body(&mut self, x: f32, y: f32, w: i32, h: i32, element_id: u8) -> u32 {
+    pub fn spawn_rigid_body(&mut self, x: f32, y: f32, w: i32, h: i32, element_id: ElementId) -> u32 {
         self.core.spawn_rigid_body(x, y, w, h, element_id)
     }
 
     /// Spawn a circular rigid body at position (x, y) with given radius
     /// Returns the body ID
-    pub fn spawn_rigid_circle(&mut self, x: f32, y: f32, radius: i32, element_id: u8) -> u32 {
+    pub fn spawn_rigid_circle(&mut self, x: f32, y: f32, radius: i32, element_id: ElementId) -> u32 {
         self.core.spawn_rigid_circle(x, y, radius, element_id)
     }
 
@@ -173,8 +430,25 @@ impl World {
         self.core.total_chunks()
     }
 
+    /// Cell details for tooltips; `undefined` outside the world
+    pub fn inspect_cell(&self, x: i32, y: i32) -> Option<CellInfo> {
+        self.core.inspect_cell(x, y)
+    }
+
+    /// Element histogram and temperature range of a rectangle
+    pub fn region_stats(&self, x: i32, y: i32, w: u32, h: u32) -> Result<RegionStats, JsValue> {
+        self.core.region_stats(x, y, w, h).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Render into a fresh RGBA8 buffer; `view`: 0 = normal, 1 = thermal, 2 = velocity, 3 = category
+    pub fn render_rgba(&self, view: u8) -> Result<Vec<u8>, JsValue> {
+        let view = RenderView::from_code(view)
+            .ok_or_else(|| JsValue::from_str(&format!("invalid render view {}", view)))?;
+        Ok(self.core.render_rgba(view))
+    }
+
     /// Get pointer to types array (for JS rendering)
-    pub fn types_ptr(&self) -> *const u8 {
+    pub fn types_ptr(&self) -> *const ElementId {
         self.core.types_ptr()
     }
 
@@ -249,6 +523,7 @@ impl World {
             types_ptr: data.types_ptr as u32,
             types_len_elements: data.types_len_elements as u32,
             types_len_bytes: data.types_len_bytes as u32,
+            types_element_bytes: data.types_element_bytes as u32,
             colors_ptr: data.colors_ptr as u32,
             colors_len_elements: data.colors_len_elements as u32,
             colors_len_bytes: data.colors_len_bytes as u32,
@@ -0,0 +1,260 @@
+//! Seeded procedural terrain: layered dirt/stone, caves, water pools,
+//! lava pockets and surface vegetation.
+//!
+//! The layout depends only on the config (including its `seed`) and the world
+//! size, never on the simulation RNG.
+
+use wasm_bindgen::prelude::*;
+
+use crate::elements::{ElementId, EL_DIRT, EL_EMPTY, EL_LAVA, EL_PLANT, EL_SEED, EL_STONE, EL_WATER};
+
+use super::WorldCore;
+
+/// Generator parameters; fractions are relative to the world height
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TerrainConfig {
+    pub seed: u64,
+    /// Average surface height, as a fraction of the world height from the top
+    pub surface_level: f32,
+    /// Max deviation of the surface from `surface_level` (fraction of height)
+    pub surface_amplitude: f32,
+    /// Dirt thickness below the surface, in cells
+    pub dirt_depth: u32,
+    /// Share of the stone layer turned into dirt strata (0..1)
+    pub strata: f32,
+    /// Width of cave tunnels (0 = no caves, 0.3 = very porous)
+    pub cave_density: f32,
+    pub water_pools: u32,
+    pub lava_pockets: u32,
+    /// Chance per surface column to grow a plant or drop a seed (0..1)
+    pub vegetation: f32,
+    pub stone_element: ElementId,
+    pub dirt_element: ElementId,
+    pub water_element: ElementId,
+    pub lava_element: ElementId,
+    pub seed_element: ElementId,
+    pub plant_element: ElementId,
+}
+
+#[wasm_bindgen]
+impl TerrainConfig {
+    /// Defaults tuned for the built-in content
+    #[wasm_bindgen(constructor)]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            surface_level: 0.4,
+            surface_amplitude: 0.1,
+            dirt_depth: 6,
+            strata: 0.2,
+            cave_density: 0.08,
+            water_pools: 3,
+            lava_pockets: 3,
+            vegetation: 0.2,
+            stone_element: EL_STONE,
+            dirt_element: EL_DIRT,
+            water_element: EL_WATER,
+            lava_element: EL_LAVA,
+            seed_element: EL_SEED,
+            plant_element: EL_PLANT,
+        }
+    }
+}
+
+impl Default for TerrainConfig {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl TerrainConfig {
+    fn elements(&self) -> [ElementId; 6] {
+        [
+            self.stone_element,
+            self.dirt_element,
+            self.water_element,
+            self.lava_element,
+            self.seed_element,
+            self.plant_element,
+        ]
+    }
+}
+
+/// splitmix64 step: small, seedable, good enough for layout decisions
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1)
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform in `lo..hi` (`lo` if the range is empty)
+    fn range(&mut self, lo: i32, hi: i32) -> i32 {
+        if hi <= lo {
+            return lo;
+        }
+        lo + (self.next_u64() % (hi - lo) as u64) as i32
+    }
+}
+
+/// Lattice value in [0, 1) for integer coordinates
+fn lattice(seed: u64, x: i32, y: i32) -> f32 {
+    let key = seed ^ (((x as u32 as u64) << 32) | y as u32 as u64).wrapping_mul(0xD6E8_FEB8_6659_FD93);
+    SplitMix(key).next_f32()
+}
+
+/// Bilinear value noise with smoothstep fade, in [0, 1)
+fn value_noise(seed: u64, x: f32, y: f32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (sx, sy) = (fx * fx * (3.0 - 2.0 * fx), fy * fy * (3.0 - 2.0 * fy));
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let top = lattice(seed, ix, iy) + (lattice(seed, ix + 1, iy) - lattice(seed, ix, iy)) * sx;
+    let bottom = lattice(seed, ix, iy + 1) + (lattice(seed, ix + 1, iy + 1) - lattice(seed, ix, iy + 1)) * sx;
+    top + (bottom - top) * sy
+}
+
+/// Three octaves of value noise, normalized to [0, 1)
+fn fbm(seed: u64, x: f32, y: f32) -> f32 {
+    let mut sum = 0.0;
+    let mut amp = 1.0;
+    let mut freq = 1.0;
+    for octave in 0..3u64 {
+        sum += value_noise(seed.wrapping_add(octave), x * freq, y * freq) * amp;
+        amp *= 0.5;
+        freq *= 2.0;
+    }
+    sum / 1.75
+}
+
+// Independent noise fields derived from the config seed
+const SALT_SURFACE: u64 = 0x5EED_0001;
+const SALT_STRATA: u64 = 0x5EED_0002;
+const SALT_CAVES: u64 = 0x5EED_0003;
+const SALT_DIRT: u64 = 0x5EED_0004;
+const SALT_FEATURES: u64 = 0x5EED_0005;
+
+/// Build the cell layout (row-major) for a `width` x `height` world
+fn layout(config: &TerrainConfig, width: u32, height: u32) -> Vec<ElementId> {
+    let (w, h) = (width as i32, height as i32);
+    let mut cells = vec![EL_EMPTY; width as usize * height as usize];
+    let at = |x: i32, y: i32| (y * w + x) as usize;
+    let seed = config.seed;
+
+    // Surface line from 1D noise
+    let base = h as f32 * config.surface_level.clamp(0.0, 1.0);
+    let amp = h as f32 * config.surface_amplitude.max(0.0);
+    let surface: Vec<i32> = (0..w)
+        .map(|x| {
+            let n = fbm(seed ^ SALT_SURFACE, x as f32 / 48.0, 0.0);
+            ((base + (n - 0.5) * 2.0 * amp).round() as i32).clamp(0, h)
+        })
+        .collect();
+
+    // Dirt cap, then stone with dirt strata, with caves carved out below the cap.
+    // No column is deeper than the world, so this also keeps the sums below in range.
+    let dirt_depth = config.dirt_depth.min(height) as i32;
+    for x in 0..w {
+        for y in surface[x as usize]..h {
+            let depth = y - surface[x as usize];
+            let jitter = (fbm(seed ^ SALT_DIRT, x as f32 / 12.0, 0.0) * 4.0) as i32;
+            let strata = fbm(seed ^ SALT_STRATA, x as f32 / 40.0, y as f32 / 6.0);
+            let element = if depth < dirt_depth + jitter || strata < config.strata.clamp(0.0, 1.0) {
+                config.dirt_element
+            } else {
+                config.stone_element
+            };
+            // Tunnels follow the noise's mid-value contour
+            let cave = fbm(seed ^ SALT_CAVES, x as f32 / 24.0, y as f32 / 16.0);
+            let carved = depth > dirt_depth && (cave - 0.5).abs() < config.cave_density * 0.5;
+            cells[at(x, y)] = if carved { EL_EMPTY } else { element };
+        }
+    }
+
+    let mut rng = SplitMix(seed ^ SALT_FEATURES);
+
+    // Water pools: shallow basins dug into the surface
+    for _ in 0..config.water_pools {
+        let cx = rng.range(0, w);
+        let half = rng.range(4, 4 + w / 16 + 1);
+        let depth = rng.range(2, 2 + half / 2 + 1);
+        for x in (cx - half).max(0)..(cx + half + 1).min(w) {
+            let t = (x - cx) as f32 / half as f32;
+            let d = (depth as f32 * (1.0 - t * t)).round() as i32;
+            let top = surface[x as usize];
+            for y in top..(top + d).min(h) {
+                cells[at(x, y)] = config.water_element;
+            }
+        }
+    }
+
+    // Lava pockets: blobs in the lower third
+    for _ in 0..config.lava_pockets {
+        let cx = rng.range(0, w);
+        let cy = rng.range(h * 2 / 3, h);
+        let r = rng.range(2, 6);
+        for y in (cy - r).max(0)..(cy + r + 1).min(h) {
+            for x in (cx - r).max(0)..(cx + r + 1).min(w) {
+                let (dx, dy) = (x - cx, y - cy);
+                if dx * dx + dy * dy <= r * r && y > surface[x as usize] {
+                    cells[at(x, y)] = config.lava_element;
+                }
+            }
+        }
+    }
+
+    // Vegetation on dry dirt: short plants, or a seed waiting to sprout
+    let vegetation = config.vegetation.clamp(0.0, 1.0);
+    for x in 0..w {
+        let top = surface[x as usize];
+        if top <= 0 || top >= h || cells[at(x, top)] != config.dirt_element {
+            continue;
+        }
+        let roll = rng.next_f32();
+        if roll < vegetation * 0.7 {
+            let stalk = rng.range(1, 4);
+            for y in (top - stalk).max(0)..top {
+                cells[at(x, y)] = config.plant_element;
+            }
+        } else if roll < vegetation {
+            cells[at(x, top - 1)] = config.seed_element;
+        }
+    }
+
+    cells
+}
+
+/// Replace the world's contents with generated terrain (user edit: one undo step).
+/// Returns the number of particles placed.
+pub(super) fn generate_terrain(world: &mut WorldCore, config: &TerrainConfig) -> Result<u32, String> {
+    for id in config.elements() {
+        if id == EL_EMPTY || !world.content.is_valid_element_id(id) {
+            return Err(format!("invalid terrain element id {}", id));
+        }
+    }
+    let (width, height) = (world.grid.width(), world.grid.height());
+    let cells = layout(config, width, height);
+
+    super::commands::clear(world);
+    let mut placed = 0u32;
+    for (idx, &element) in cells.iter().enumerate() {
+        if element == EL_EMPTY {
+            continue;
+        }
+        let (x, y) = world.grid.coords(idx);
+        if super::commands::add_particle(world, x, y, element) {
+            placed += 1;
+        }
+    }
+    Ok(placed)
+}
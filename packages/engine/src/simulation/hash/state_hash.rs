@@ -0,0 +1,159 @@
+//! World state hashing for desync and regression detection.
+//!
+//! Hashes are FNV-1a (64-bit) over the raw bits of the grid arrays, so they are
+//! stable across platforms and runs. `updated` is excluded: it is scratch state
+//! reset at the start of every step.
+
+use crate::chunks::CHUNK_SIZE;
+use crate::elements::ElementId;
+use crate::grid::Grid;
+use crate::rigid_body_system::RigidBodySystem;
+
+use super::WorldCore;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct StateHasher(u64);
+
+impl StateHasher {
+    fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    #[inline]
+    fn u8(&mut self, v: u8) {
+        self.write(&[v]);
+    }
+
+    #[inline]
+    fn element(&mut self, id: ElementId) {
+        self.write(&id.to_le_bytes());
+    }
+
+    #[inline]
+    fn u16(&mut self, v: u16) {
+        self.write(&v.to_le_bytes());
+    }
+
+    #[inline]
+    fn u32(&mut self, v: u32) {
+        self.write(&v.to_le_bytes());
+    }
+
+    #[inline]
+    fn i32(&mut self, v: i32) {
+        self.write(&v.to_le_bytes());
+    }
+
+    #[inline]
+    fn u64(&mut self, v: u64) {
+        self.write(&v.to_le_bytes());
+    }
+
+    #[inline]
+    fn f32(&mut self, v: f32) {
+        self.u32(v.to_bits());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Hash of the whole simulation state: grid (via chunk hashes), rigid bodies, RNG streams
+pub(super) fn state_hash(world: &WorldCore) -> u64 {
+    let mut h = StateHasher::new();
+    h.u32(world.grid.width());
+    h.u32(world.grid.height());
+    for chunk in chunk_hashes(world) {
+        h.u64(chunk);
+    }
+    hash_rigid_bodies(&mut h, &world.rigid_bodies);
+    h.u64(world.rng.seed);
+    h.u32(world.rng.behaviors);
+    h.u32(world.rng.reactions);
+    h.u32(world.rng.temperature);
+    h.finish()
+}
+
+/// One hash per chunk, row-major (`cy * chunks_x + cx`), over that chunk's cells
+pub(super) fn chunk_hashes(world: &WorldCore) -> Vec<u64> {
+    let grid = &world.grid;
+    let (chunks_x, chunks_y) = world.chunks.dimensions();
+
+    let mut out = Vec::with_capacity((chunks_x * chunks_y) as usize);
+    for cy in 0..chunks_y {
+        for cx in 0..chunks_x {
+            out.push(hash_chunk(grid, cx, cy));
+        }
+    }
+    out
+}
+
+fn hash_chunk(grid: &Grid, cx: u32, cy: u32) -> u64 {
+    let x0 = cx * CHUNK_SIZE;
+    let y0 = cy * CHUNK_SIZE;
+    let x1 = (x0 + CHUNK_SIZE).min(grid.width());
+    let y1 = (y0 + CHUNK_SIZE).min(grid.height());
+
+    let mut h = StateHasher::new();
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let idx = grid.index(x, y);
+            h.element(grid.types[idx]);
+            h.u32(grid.colors[idx]);
+            h.u16(grid.life[idx]);
+            h.f32(grid.temperature[idx]);
+            h.f32(grid.vx[idx]);
+            h.f32(grid.vy[idx]);
+        }
+    }
+    h.finish()
+}
+
+fn hash_rigid_bodies(h: &mut StateHasher, system: &RigidBodySystem) {
+    h.u32(system.next_id());
+    h.u32(system.bodies().len() as u32);
+    for body in system.bodies() {
+        h.u32(body.id);
+        h.u8(body.active as u8);
+        for v in [
+            body.pos.x,
+            body.pos.y,
+            body.velocity.x,
+            body.velocity.y,
+            body.angle,
+            body.angular_vel,
+            body.mass,
+            body.moment_of_inertia,
+            body.half_width,
+            body.half_height,
+            body.prev_pos.x,
+            body.prev_pos.y,
+            body.prev_angle,
+            body.restitution,
+        ] {
+            h.f32(v);
+        }
+        h.u32(body.pixels.len() as u32);
+        for p in body.pixels.iter() {
+            h.write(&[p.dx as u8, p.dy as u8]);
+            h.element(p.element);
+            h.u8(p.color_seed);
+        }
+        h.u32(body.prev_world_coords.len() as u32);
+        for &(x, y) in body.prev_world_coords.iter() {
+            h.i32(x);
+            h.i32(y);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Undo/redo history for world-editing commands.
+//!
+//! Each stroke stores the state a cell had *before* its first edit in that stroke.
+//! Undo swaps those states back into the grid; the swapped-out (current) states are
+//! kept in the same record, so redo is the exact same swap in the other direction.
+//!
+//! Records are bounded by a byte budget. A stroke larger than the whole budget
+//! (a `clear` or terrain generation on a large world) evicts everything, itself
+//! included, so that step cannot be undone and `can_undo()` reports false.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::elements::{ElementId, EL_EMPTY};
+use crate::grid::Grid;
+use crate::rigid_body_system::RigidBodySystem;
+
+use super::WorldCore;
+
+/// Default memory budget for undo + redo records (16 MiB)
+pub(crate) const DEFAULT_HISTORY_BUDGET_BYTES: usize = 16 * 1024 * 1024;
+
+/// Everything a user edit can change about a cell
+#[derive(Clone, Copy)]
+struct CellState {
+    element: ElementId,
+    life: u16,
+    color: u32,
+    temperature: f32,
+    vx: f32,
+    vy: f32,
+}
+
+impl CellState {
+    #[inline]
+    fn read(grid: &Grid, idx: usize) -> Self {
+        Self {
+            element: grid.types[idx],
+            life: grid.life[idx],
+            color: grid.colors[idx],
+            temperature: grid.temperature[idx],
+            vx: grid.vx[idx],
+            vy: grid.vy[idx],
+        }
+    }
+
+    #[inline]
+    fn write(&self, grid: &mut Grid, idx: usize) {
+        grid.types[idx] = self.element;
+        grid.life[idx] = self.life;
+        grid.colors[idx] = self.color;
+        grid.temperature[idx] = self.temperature;
+        grid.vx[idx] = self.vx;
+        grid.vy[idx] = self.vy;
+        grid.updated[idx] = 0;
+    }
+}
+
+/// One undoable step (a brush drag, a single command, a clear)
+struct Stroke {
+    cells: Vec<(u32, CellState)>,
+    /// Rigid bodies replaced by this stroke (only `clear()` does that)
+    bodies: Option<RigidBodySystem>,
+}
+
+impl Stroke {
+    fn bytes(&self) -> usize {
+        self.cells.len() * std::mem::size_of::<(u32, CellState)>() + std::mem::size_of::<Self>()
+    }
+}
+
+pub(crate) struct EditHistory {
+    undo: VecDeque<Stroke>,
+    redo: Vec<Stroke>,
+    open: Option<Stroke>,
+    touched: HashSet<u32>,
+    /// Stroke was opened by a single command rather than `begin_stroke`
+    implicit: bool,
+    budget_bytes: usize,
+    used_bytes: usize,
+}
+
+impl EditHistory {
+    pub(crate) fn new() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            open: None,
+            touched: HashSet::new(),
+            implicit: false,
+            budget_bytes: DEFAULT_HISTORY_BUDGET_BYTES,
+            used_bytes: 0,
+        }
+    }
+
+    /// Remember the current state of `(x, y)` if a stroke is open and the cell
+    /// was not edited earlier in the same stroke. Call right before mutating.
+    #[inline]
+    pub(crate) fn record_cell(&mut self, grid: &Grid, x: u32, y: u32) {
+        let Some(stroke) = self.open.as_mut() else {
+            return;
+        };
+        let idx = grid.index(x, y);
+        if self.touched.insert(idx as u32) {
+            stroke.cells.push((idx as u32, CellState::read(grid, idx)));
+        }
+    }
+
+    /// Keep the rigid body system that is being replaced
+    pub(crate) fn record_bodies(&mut self, bodies: RigidBodySystem) {
+        if let Some(stroke) = self.open.as_mut() {
+            if stroke.bodies.is_none() {
+                stroke.bodies = Some(bodies);
+            }
+        }
+    }
+
+    pub(crate) fn begin_stroke(&mut self) {
+        if self.open.is_some() {
+            if !self.implicit {
+                return;
+            }
+            self.end_stroke();
+        }
+        self.open = Some(Stroke { cells: Vec::new(), bodies: None });
+        self.implicit = false;
+    }
+
+    /// Opens a single-command stroke unless the user already has one open
+    fn begin_implicit(&mut self) -> bool {
+        if self.open.is_some() {
+            return false;
+        }
+        self.open = Some(Stroke { cells: Vec::new(), bodies: None });
+        self.implicit = true;
+        true
+    }
+
+    pub(crate) fn end_stroke(&mut self) {
+        let Some(stroke) = self.open.take() else {
+            return;
+        };
+        self.touched.clear();
+        self.implicit = false;
+        if stroke.cells.is_empty() && stroke.bodies.is_none() {
+            return;
+        }
+
+        // A new edit invalidates everything that could be redone.
+        let dropped: usize = self.redo.drain(..).map(|s| s.bytes()).sum();
+        self.used_bytes = self.used_bytes.saturating_sub(dropped) + stroke.bytes();
+        self.undo.push_back(stroke);
+        self.enforce_budget();
+    }
+
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub(crate) fn set_budget(&mut self, bytes: usize) {
+        self.budget_bytes = bytes;
+        self.enforce_budget();
+    }
+
+    /// Drop all records (grid indices/ids no longer valid)
+    pub(crate) fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.open = None;
+        self.touched.clear();
+        self.implicit = false;
+        self.used_bytes = 0;
+    }
+
+    fn enforce_budget(&mut self) {
+        while self.used_bytes > self.budget_bytes {
+            // Oldest undo steps go first, then redo steps.
+            let freed = if let Some(s) = self.undo.pop_front() {
+                s.bytes()
+            } else if !self.redo.is_empty() {
+                self.redo.remove(0).bytes()
+            } else {
+                break;
+            };
+            self.used_bytes = self.used_bytes.saturating_sub(freed);
+        }
+    }
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a user command inside its own undo step unless a stroke is already open
+pub(super) fn recorded<R>(world: &mut WorldCore, f: impl FnOnce(&mut WorldCore) -> R) -> R {
+    let implicit = world.history.begin_implicit();
+    let out = f(world);
+    if implicit {
+        world.history.end_stroke();
+    }
+    out
+}
+
+pub(super) fn undo(world: &mut WorldCore) -> bool {
+    world.history.end_stroke();
+    let Some(mut stroke) = world.history.undo.pop_back() else {
+        return false;
+    };
+    swap_stroke(world, &mut stroke);
+    world.history.redo.push(stroke);
+    true
+}
+
+pub(super) fn redo(world: &mut WorldCore) -> bool {
+    world.history.end_stroke();
+    let Some(mut stroke) = world.history.redo.pop() else {
+        return false;
+    };
+    swap_stroke(world, &mut stroke);
+    world.history.undo.push_back(stroke);
+    true
+}
+
+/// Exchange recorded and current cell states (undo and redo are the same operation)
+fn swap_stroke(world: &mut WorldCore, stroke: &mut Stroke) {
+    let grid = &mut world.grid;
+    for (idx, saved) in stroke.cells.iter_mut() {
+        let idx = *idx as usize;
+        if idx >= grid.size() {
+            continue;
+        }
+        let current = CellState::read(grid, idx);
+        saved.write(grid, idx);
+
+        let was_empty = current.element == EL_EMPTY;
+        let now_empty = saved.element == EL_EMPTY;
+        if was_empty && !now_empty {
+            world.particle_count = world.particle_count.saturating_add(1);
+        } else if !was_empty && now_empty {
+            world.particle_count = world.particle_count.saturating_sub(1);
+        }
+        *saved = current;
+    }
+
+    if let Some(bodies) = stroke.bodies.as_mut() {
+        std::mem::swap(&mut world.rigid_bodies, bodies);
+    }
+}
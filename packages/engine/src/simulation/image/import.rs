@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::domain::content::ContentRegistry;
+use crate::elements::{ElementId, EL_EMPTY};
+
+use super::WorldCore;
+
+/// Settings for `import_image`
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ImageImportOptions {
+    palette: Option<Vec<ElementId>>,
+    alpha_threshold: u8,
+}
+
+#[wasm_bindgen]
+impl ImageImportOptions {
+    /// All visible elements, pixels with alpha < 128 become empty
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            palette: None,
+            alpha_threshold: 128,
+        }
+    }
+
+    /// Restrict matching to these element ids (empty = all visible elements)
+    pub fn set_palette(&mut self, ids: Vec<ElementId>) {
+        self.palette = if ids.is_empty() { None } else { Some(ids) };
+    }
+
+    /// Pixels with alpha below this value become empty cells
+    pub fn set_alpha_threshold(&mut self, alpha: u8) {
+        self.alpha_threshold = alpha;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn alpha_threshold(&self) -> u8 {
+        self.alpha_threshold
+    }
+}
+
+impl ImageImportOptions {
+    pub(crate) fn palette(&self) -> Option<&[ElementId]> {
+        self.palette.as_deref()
+    }
+}
+
+impl Default for ImageImportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Candidate element with its base color split into channels
+struct PaletteEntry {
+    id: ElementId,
+    r: i32,
+    g: i32,
+    b: i32,
+}
+
+fn build_palette(content: &ContentRegistry, options: &ImageImportOptions) -> Result<Vec<PaletteEntry>, String> {
+    let ids: Vec<ElementId> = match options.palette.as_ref() {
+        Some(ids) => {
+            for &id in ids {
+                if id == EL_EMPTY || !content.is_valid_element_id(id) {
+                    return Err(format!("invalid palette element id {}", id));
+                }
+            }
+            ids.clone()
+        }
+        None => (0..content.element_count())
+            .map(|idx| idx as ElementId)
+            .filter(|&id| id != EL_EMPTY && !content.is_hidden(id))
+            .collect(),
+    };
+
+    let palette: Vec<PaletteEntry> = ids
+        .into_iter()
+        .filter_map(|id| {
+            // Element colors are 0xAARRGGBB
+            let c = content.props(id)?.color;
+            Some(PaletteEntry {
+                id,
+                r: ((c >> 16) & 0xFF) as i32,
+                g: ((c >> 8) & 0xFF) as i32,
+                b: (c & 0xFF) as i32,
+            })
+        })
+        .collect();
+
+    if palette.is_empty() {
+        return Err("image import palette is empty".to_string());
+    }
+    Ok(palette)
+}
+
+fn closest(palette: &[PaletteEntry], r: u8, g: u8, b: u8) -> ElementId {
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    let mut best = palette[0].id;
+    let mut best_dist = i32::MAX;
+    for entry in palette {
+        let (dr, dg, db) = (entry.r - r, entry.g - g, entry.b - b);
+        let dist = dr * dr + dg * dg + db * db;
+        if dist < best_dist {
+            best_dist = dist;
+            best = entry.id;
+        }
+    }
+    best
+}
+
+/// Convert an RGBA buffer (row-major, 4 bytes per pixel) into particles with the
+/// image's top-left corner at `(x, y)`.
+///
+/// Opaque pixels become the element with the nearest base color (squared RGB distance);
+/// transparent pixels become empty cells. Pixels outside the world are ignored.
+/// Returns the number of particles placed.
+pub(super) fn import_image(
+    world: &mut WorldCore,
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+    options: &ImageImportOptions,
+) -> Result<u32, String> {
+    let expected = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|n| n.checked_mul(4));
+    if expected != Some(rgba.len()) {
+        return Err(format!(
+            "RGBA buffer has {} bytes, expected {}x{}x4",
+            rgba.len(),
+            width,
+            height
+        ));
+    }
+    let palette = build_palette(&world.content, options)?;
+
+    // Pixel art reuses few colors
+    let mut cache: HashMap<u32, ElementId> = HashMap::new();
+    let mut placed = 0u32;
+    for py in 0..height {
+        for px in 0..width {
+            // i64: offsets near i32::MAX must not overflow
+            let gx = x as i64 + px as i64;
+            let gy = y as i64 + py as i64;
+            if !(0..world.grid.width() as i64).contains(&gx) || !(0..world.grid.height() as i64).contains(&gy) {
+                continue;
+            }
+            let i = (py as usize * width as usize + px as usize) * 4;
+            let (r, g, b, a) = (rgba[i], rgba[i + 1], rgba[i + 2], rgba[i + 3]);
+
+            let (gx, gy) = (gx as u32, gy as u32);
+            super::commands::remove_particle(world, gx, gy);
+            if a < options.alpha_threshold {
+                continue;
+            }
+            let rgb = u32::from_le_bytes([r, g, b, 0]);
+            let element = *cache.entry(rgb).or_insert_with(|| closest(&palette, r, g, b));
+            if super::commands::add_particle(world, gx, gy, element) {
+                placed += 1;
+            }
+        }
+    }
+    Ok(placed)
+}
@@ -6,22 +6,27 @@ use crate::domain::content::ContentRegistry;
 use crate::grid::Grid;
 use crate::rigid_body_system::RigidBodySystem;
 
+use super::history::EditHistory;
 use super::perf_stats::PerfStats;
+use super::random::{RngStreams, DEFAULT_SEED};
 use super::WorldCore;
 
 pub(super) fn create_world_core(width: u32, height: u32) -> WorldCore {
     WorldCore {
         content: Arc::new(ContentRegistry::from_generated()),
+        content_base: None,
         grid: Grid::new(width, height),
         chunks: ChunkGrid::new(width, height),
         behaviors: BehaviorRegistry::new(),
         rigid_bodies: RigidBodySystem::new(),
+        history: EditHistory::new(),
+        recorder: None,
         gravity_x: 0.0,
         gravity_y: 1.0,
         ambient_temperature: 20.0,
         particle_count: 0,
         frame: 0,
-        rng_state: 12345,
+        rng: RngStreams::from_seed(DEFAULT_SEED),
         perf_enabled: false,
         perf_detailed: false,
         perf_split: false,
@@ -7,3 +7,54 @@ pub(crate) fn xorshift32(state: &mut u32) -> u32 {
     *state = x;
     x
 }
+
+/// Seed used by `WorldCore::new` (matches the historical fixed RNG state)
+pub(crate) const DEFAULT_SEED: u64 = 12345;
+
+/// Independent xorshift32 states, one per subsystem.
+///
+/// Each subsystem draws only from its own stream, so e.g. a new reaction firing
+/// does not shift the sequence powder/liquid behaviors see.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct RngStreams {
+    pub(crate) seed: u64,
+    pub(crate) behaviors: u32,
+    pub(crate) reactions: u32,
+    pub(crate) temperature: u32,
+}
+
+impl RngStreams {
+    pub(crate) fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            behaviors: stream_state(seed, 1),
+            reactions: stream_state(seed, 2),
+            temperature: stream_state(seed, 3),
+        }
+    }
+
+    /// Rebuild saved streams; zero (stuck) states are re-derived from the seed
+    pub(crate) fn restore(seed: u64, behaviors: u32, reactions: u32, temperature: u32) -> Self {
+        let derived = Self::from_seed(seed);
+        let pick = |saved: u32, fallback: u32| if saved == 0 { fallback } else { saved };
+        Self {
+            seed,
+            behaviors: pick(behaviors, derived.behaviors),
+            reactions: pick(reactions, derived.reactions),
+            temperature: pick(temperature, derived.temperature),
+        }
+    }
+}
+
+/// Derive a non-zero xorshift32 state for `stream` (splitmix64 finalizer)
+fn stream_state(seed: u64, stream: u64) -> u32 {
+    let mut z = seed.wrapping_add(stream.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    // xorshift32 gets stuck at zero
+    match (z ^ (z >> 32)) as u32 {
+        0 => 0x9E37_79B9,
+        s => s,
+    }
+}
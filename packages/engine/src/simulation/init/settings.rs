@@ -1,4 +1,5 @@
 use super::perf_stats::PerfStats;
+use super::random::RngStreams;
 use super::WorldCore;
 
 pub(super) fn enable_perf_metrics(world: &mut WorldCore, enabled: bool) {
@@ -29,3 +30,11 @@ pub(super) fn set_ambient_temperature(world: &mut WorldCore, temp: f32) {
 pub(super) fn get_ambient_temperature(world: &WorldCore) -> f32 {
     world.ambient_temperature
 }
+
+pub(super) fn set_seed(world: &mut WorldCore, seed: u64) {
+    world.rng = RngStreams::from_seed(seed);
+}
+
+pub(super) fn get_seed(world: &WorldCore) -> u64 {
+    world.rng.seed
+}
@@ -0,0 +1,149 @@
+//! Read-only queries for tooltips and debug overlays.
+
+use wasm_bindgen::prelude::*;
+
+use crate::elements::ElementId;
+
+use super::WorldCore;
+
+/// Everything stored for one grid cell
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellInfo {
+    x: u32,
+    y: u32,
+    element: ElementId,
+    key: String,
+    temperature: f32,
+    life: u16,
+    vx: f32,
+    vy: f32,
+    rigid_body: Option<u32>,
+}
+
+#[wasm_bindgen]
+impl CellInfo {
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> u32 { self.x }
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> u32 { self.y }
+    #[wasm_bindgen(getter)]
+    pub fn element(&self) -> ElementId { self.element }
+    /// Content key (empty string if the id has no key)
+    #[wasm_bindgen(getter)]
+    pub fn key(&self) -> String { self.key.clone() }
+    #[wasm_bindgen(getter)]
+    pub fn temperature(&self) -> f32 { self.temperature }
+    #[wasm_bindgen(getter)]
+    pub fn life(&self) -> u16 { self.life }
+    #[wasm_bindgen(getter)]
+    pub fn vx(&self) -> f32 { self.vx }
+    #[wasm_bindgen(getter)]
+    pub fn vy(&self) -> f32 { self.vy }
+    /// ID of the rigid body covering this cell, if any
+    #[wasm_bindgen(getter)]
+    pub fn rigid_body(&self) -> Option<u32> { self.rigid_body }
+}
+
+/// Aggregates over a rectangle (cells outside the world are not counted)
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionStats {
+    cells: u32,
+    /// Per element id, including empty
+    counts: Vec<u32>,
+    avg_temperature: f32,
+    min_temperature: f32,
+    max_temperature: f32,
+}
+
+#[wasm_bindgen]
+impl RegionStats {
+    /// In-bounds cells in the region
+    #[wasm_bindgen(getter)]
+    pub fn cells(&self) -> u32 { self.cells }
+    /// Average temperature over particles and air
+    #[wasm_bindgen(getter)]
+    pub fn avg_temperature(&self) -> f32 { self.avg_temperature }
+    #[wasm_bindgen(getter)]
+    pub fn min_temperature(&self) -> f32 { self.min_temperature }
+    #[wasm_bindgen(getter)]
+    pub fn max_temperature(&self) -> f32 { self.max_temperature }
+
+    pub fn count(&self, element: ElementId) -> u32 {
+        self.counts.get(element as usize).copied().unwrap_or(0)
+    }
+
+    /// Element ids present in the region, ascending (pairs with `element_counts`)
+    pub fn element_ids(&self) -> Vec<ElementId> {
+        (0..self.counts.len())
+            .filter(|&id| self.counts[id] > 0)
+            .map(|id| id as ElementId)
+            .collect()
+    }
+
+    /// Cell count for each id of `element_ids`
+    pub fn element_counts(&self) -> Vec<u32> {
+        self.counts.iter().copied().filter(|&n| n > 0).collect()
+    }
+}
+
+pub(super) fn inspect_cell(world: &WorldCore, x: i32, y: i32) -> Option<CellInfo> {
+    let grid = &world.grid;
+    if !grid.in_bounds(x, y) {
+        return None;
+    }
+    let idx = grid.index(x as u32, y as u32);
+    let element = grid.types[idx];
+    Some(CellInfo {
+        x: x as u32,
+        y: y as u32,
+        element,
+        key: world.content.key_by_id(element).unwrap_or_default().to_string(),
+        temperature: grid.temperature[idx],
+        life: grid.life[idx],
+        vx: grid.vx[idx],
+        vy: grid.vy[idx],
+        rigid_body: world.rigid_bodies.body_at(x, y),
+    })
+}
+
+pub(super) fn region_stats(world: &WorldCore, x: i32, y: i32, w: u32, h: u32) -> Result<RegionStats, String> {
+    if w == 0 || h == 0 {
+        return Err(format!("invalid region size {}x{}", w, h));
+    }
+    let grid = &world.grid;
+    let x0 = (x as i64).max(0);
+    let y0 = (y as i64).max(0);
+    let x1 = (x as i64 + w as i64).min(grid.width() as i64);
+    let y1 = (y as i64 + h as i64).min(grid.height() as i64);
+    if x0 >= x1 || y0 >= y1 {
+        return Err("region is outside the world".to_string());
+    }
+
+    let mut counts = vec![0u32; world.content.element_count()];
+    let mut sum = 0.0f64;
+    let mut min = f32::INFINITY;
+    let mut max = f32::NEG_INFINITY;
+    for gy in y0..y1 {
+        for gx in x0..x1 {
+            let idx = grid.index(gx as u32, gy as u32);
+            if let Some(n) = counts.get_mut(grid.types[idx] as usize) {
+                *n += 1;
+            }
+            let t = grid.temperature[idx];
+            sum += t as f64;
+            min = min.min(t);
+            max = max.max(t);
+        }
+    }
+
+    let cells = ((x1 - x0) * (y1 - y0)) as u32;
+    Ok(RegionStats {
+        cells,
+        counts,
+        avg_temperature: (sum / cells as f64) as f32,
+        min_temperature: min,
+        max_temperature: max,
+    })
+}
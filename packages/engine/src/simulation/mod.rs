@@ -39,16 +39,64 @@ mod step;
 mod chunk_processing;
 #[path = "commands/commands.rs"]
 mod commands;
+#[path = "commands/fill.rs"]
+mod fill;
+#[path = "commands/heat.rs"]
+mod heat;
+#[path = "commands/velocity.rs"]
+mod velocity;
+#[path = "commands/batch.rs"]
+mod batch;
 #[path = "rigid/rigid.rs"]
 mod rigid;
 #[path = "init/init.rs"]
 mod init;
 #[path = "init/settings.rs"]
 mod settings;
+#[path = "snapshot/mod.rs"]
+mod snapshot;
+#[path = "content/reload.rs"]
+mod reload;
+#[path = "content/overrides.rs"]
+mod overrides;
+#[path = "history/history.rs"]
+mod history;
+#[path = "replay/mod.rs"]
+mod replay;
+#[path = "hash/state_hash.rs"]
+mod state_hash;
+#[path = "resize/resize.rs"]
+mod resize;
+#[path = "stamp/mod.rs"]
+mod stamp;
+#[path = "image/import.rs"]
+mod image_import;
+#[path = "render/render.rs"]
+mod render;
+#[path = "brush/mod.rs"]
+mod brush;
+#[path = "inspect/inspect.rs"]
+mod inspect;
+#[path = "generate/terrain.rs"]
+mod terrain;
+#[path = "scene/scene.rs"]
+mod scene;
 mod facade;
 
 pub use facade::World;
 pub use perf_stats::PerfStats;
+pub use snapshot::SnapshotLoadReport;
+pub use replay::ReplayReport;
+pub use resize::ResizeAnchor;
+pub use stamp::{Stamp, StampBlend};
+pub use image_import::ImageImportOptions;
+pub use render::RenderView;
+pub use brush::{Brush, BrushMode, BrushShape};
+pub use inspect::{CellInfo, RegionStats};
+pub use terrain::TerrainConfig;
+pub use scene::SceneLoadReport;
+
+use replay::command::RecordedCommand;
 
 pub(crate) use perf_timer::PerfTimer;
 
@@ -59,9 +107,10 @@ fn xorshift32(state: &mut u32) -> u32 {
 }
 
 pub(crate) struct AbiLayoutData {
-    pub(crate) types_ptr: *const u8,
+    pub(crate) types_ptr: *const ElementId,
     pub(crate) types_len_elements: usize,
     pub(crate) types_len_bytes: usize,
+    pub(crate) types_element_bytes: usize,
     pub(crate) colors_ptr: *const u32,
     pub(crate) colors_len_elements: usize,
     pub(crate) colors_len_bytes: usize,
@@ -73,10 +122,13 @@ pub(crate) struct AbiLayoutData {
 /// The simulation world
 pub struct WorldCore {
     content: Arc<ContentRegistry>,
+    content_base: Option<Arc<ContentRegistry>>,  // Registry as loaded, while properties are overridden
     grid: Grid,
     chunks: ChunkGrid,
     behaviors: BehaviorRegistry,
     rigid_bodies: RigidBodySystem,  // Rigid body physics system
+    history: history::EditHistory,  // Undo/redo for user edits
+    recorder: Option<replay::InputRecorder>,  // Input log while recording
     
     // Settings
     gravity_x: f32,
@@ -86,7 +138,7 @@ pub struct WorldCore {
     // State
     particle_count: u32,
     frame: u64,
-    rng_state: u32,
+    rng: random::RngStreams,  // Per-subsystem RNG streams
 
     // Perf metrics
     perf_enabled: bool,
@@ -103,16 +155,54 @@ impl WorldCore {
     }
 
     pub fn load_content_bundle_json(&mut self, json: &str) -> Result<(), String> {
+        self.record(|| RecordedCommand::LoadContentBundle { json: json.to_string() });
         let registry = ContentRegistry::from_bundle_json(json)?;
         self.content = Arc::new(registry);
-        self.clear();
+        self.content_base = None;
+        commands::reset(self);
+        self.history.clear();
         Ok(())
     }
 
+    /// Hot-reload a content bundle without clearing the world
+    /// Particles are migrated by element key and recolored; elements missing from the
+    /// new bundle become `fallback_key` (or empty). Returns the keys that disappeared.
+    pub fn reload_content_bundle_json(&mut self, json: &str, fallback_key: Option<&str>) -> Result<Vec<String>, String> {
+        self.record(|| RecordedCommand::ReloadContentBundle {
+            json: json.to_string(),
+            fallback_key: fallback_key.map(str::to_string),
+        });
+        let disappeared = reload::reload_content_bundle_json(self, json, fallback_key)?;
+        self.history.clear();
+        Ok(disappeared)
+    }
+
     pub fn get_content_manifest_json(&self) -> String {
         self.content.manifest_json()
     }
 
+    /// Override one element property (`density`, `dispersion`, `friction`, `bounce`,
+    /// `heatConductivity` or `color`) without reloading the bundle
+    /// Values must fit the bundle field type. Color changes repaint existing particles.
+    pub fn set_element_property(&mut self, element: ElementId, field: &str, value: f64) -> Result<(), String> {
+        self.record(|| RecordedCommand::SetElementProperty { element, field: field.to_string(), value });
+        overrides::set_element_property(self, element, field, value)
+    }
+
+    /// Undo every `set_element_property` since the bundle was loaded
+    pub fn reset_element_properties(&mut self) {
+        self.record(|| RecordedCommand::ResetElementProperties);
+        overrides::reset_element_properties(self);
+    }
+
+    /// Resize the world, keeping the scene placed according to `anchor`
+    /// Cells outside the new bounds are dropped; rigid bodies are shifted and clipped.
+    /// Clears undo history
+    pub fn resize(&mut self, new_w: u32, new_h: u32, anchor: ResizeAnchor) -> Result<(), String> {
+        self.record(|| RecordedCommand::Resize { width: new_w, height: new_h, anchor: anchor.code() });
+        resize::resize(self, new_w, new_h, anchor)
+    }
+
     pub fn width(&self) -> u32 { self.grid.width() }
 
     pub fn height(&self) -> u32 { self.grid.height() }
@@ -140,59 +230,299 @@ impl WorldCore {
     }
 
     pub fn set_gravity(&mut self, x: f32, y: f32) {
+        self.record(|| RecordedCommand::SetGravity { x, y });
         settings::set_gravity(self, x, y);
     }
 
     pub fn set_ambient_temperature(&mut self, temp: f32) {
+        self.record(|| RecordedCommand::SetAmbientTemperature { temp });
         settings::set_ambient_temperature(self, temp);
     }
     
+    /// Reseed every RNG stream (behaviors, reactions, temperature)
+    /// Same seed + same inputs = same simulation
+    pub fn set_seed(&mut self, seed: u64) {
+        self.record(|| RecordedCommand::SetSeed { seed });
+        settings::set_seed(self, seed);
+    }
+
+    pub fn seed(&self) -> u64 {
+        settings::get_seed(self)
+    }
+
     /// DEBUG: Get current ambient temperature
     pub fn get_ambient_temperature(&self) -> f32 {
         settings::get_ambient_temperature(self)
     }
 
     /// Add a particle at position
-    pub fn add_particle(&mut self, x: u32, y: u32, element: u8) -> bool {
-        commands::add_particle(self, x, y, element)
+    pub fn add_particle(&mut self, x: u32, y: u32, element: ElementId) -> bool {
+        self.record(|| RecordedCommand::AddParticle { x, y, element });
+        history::recorded(self, |w| commands::add_particle(w, x, y, element))
     }
 
     /// Add particles in radius (brush)
-    pub fn add_particles_in_radius(&mut self, cx: i32, cy: i32, radius: i32, element: u8) {
-        commands::add_particles_in_radius(self, cx, cy, radius, element)
+    pub fn add_particles_in_radius(&mut self, cx: i32, cy: i32, radius: i32, element: ElementId) {
+        self.record(|| RecordedCommand::AddParticlesInRadius { cx, cy, radius, element });
+        history::recorded(self, |w| commands::add_particles_in_radius(w, cx, cy, radius, element))
+    }
+
+    /// Paint a shaped brush; cells the brush mode selects are overwritten (or erased).
+    /// Returns the number of cells that changed
+    pub fn paint(&mut self, shape: &BrushShape, brush: &Brush) -> Result<u32, String> {
+        let (mode, target) = brush.mode().code();
+        self.record(|| RecordedCommand::Paint { shape: shape.clone(), element: brush.element(), mode, target });
+        history::recorded(self, |world| brush::paint::paint(world, shape, brush))
     }
 
     /// Remove particle at position
     pub fn remove_particle(&mut self, x: u32, y: u32) -> bool {
-        commands::remove_particle(self, x, y)
+        self.record(|| RecordedCommand::RemoveParticle { x, y });
+        history::recorded(self, |w| commands::remove_particle(w, x, y))
     }
 
     /// Remove particles in radius
     pub fn remove_particles_in_radius(&mut self, cx: i32, cy: i32, radius: i32) {
-        commands::remove_particles_in_radius(self, cx, cy, radius)
+        self.record(|| RecordedCommand::RemoveParticlesInRadius { cx, cy, radius });
+        history::recorded(self, |w| commands::remove_particles_in_radius(w, cx, cy, radius))
+    }
+
+    /// Temperature brush: add `delta` degrees to particles and air in radius.
+    /// `falloff` fades the change towards the rim. Phase changes follow on the next step
+    pub fn apply_heat_in_radius(&mut self, cx: i32, cy: i32, radius: i32, delta: f32, falloff: bool) {
+        self.record(|| RecordedCommand::ApplyHeatInRadius { cx, cy, radius, delta, falloff });
+        history::recorded(self, |w| heat::apply_heat_in_radius(w, cx, cy, radius, delta, falloff))
+    }
+
+    /// Set particles and air in radius to `temp`
+    pub fn set_temperature_in_radius(&mut self, cx: i32, cy: i32, radius: i32, temp: f32) {
+        self.record(|| RecordedCommand::SetTemperatureInRadius { cx, cy, radius, temp });
+        history::recorded(self, |w| heat::set_temperature_in_radius(w, cx, cy, radius, temp))
+    }
+
+    /// Add velocity `(vx, vy)` to movable particles in radius (throw / blower tool).
+    /// Returns the number of particles pushed
+    pub fn apply_impulse_in_radius(&mut self, cx: i32, cy: i32, radius: i32, vx: f32, vy: f32) -> u32 {
+        self.record(|| RecordedCommand::ApplyImpulse { cx, cy, radius, vx, vy });
+        history::recorded(self, |w| velocity::apply_impulse_in_radius(w, cx, cy, radius, vx, vy))
+    }
+
+    /// Radial outward push that fades towards the rim (explosion tool)
+    pub fn apply_blast(&mut self, cx: i32, cy: i32, radius: i32, strength: f32) -> u32 {
+        self.record(|| RecordedCommand::ApplyBlast { cx, cy, radius, strength });
+        history::recorded(self, |w| velocity::apply_blast(w, cx, cy, radius, strength))
+    }
+
+    /// Tangential swirl around the center; positive strength is clockwise on screen
+    pub fn apply_vortex(&mut self, cx: i32, cy: i32, radius: i32, strength: f32) -> u32 {
+        self.record(|| RecordedCommand::ApplyVortex { cx, cy, radius, strength });
+        history::recorded(self, |w| velocity::apply_vortex(w, cx, cy, radius, strength))
+    }
+
+    /// Fill the connected region under `(x, y)` with `element` (fill tool).
+    /// `connectivity` is 4 or 8; regions larger than `max_cells` are left untouched (error).
+    /// Returns the number of cells changed
+    pub fn flood_fill(&mut self, x: u32, y: u32, element: ElementId, connectivity: u8, max_cells: u32) -> Result<u32, String> {
+        self.record(|| RecordedCommand::FloodFill { x, y, element, connectivity, max_cells });
+        history::recorded(self, |w| fill::flood_fill(w, x, y, element, connectivity, max_cells))
+    }
+
+    /// Replace everything with seeded procedural terrain (one undo step).
+    /// Returns the number of particles placed
+    pub fn generate_terrain(&mut self, config: &TerrainConfig) -> Result<u32, String> {
+        self.record(|| RecordedCommand::GenerateTerrain { config: *config });
+        history::recorded(self, |world| terrain::generate_terrain(world, config))
+    }
+
+    /// Run a packed batch of edit commands (recording wire format) in order.
+    /// Returns one result per command; a malformed batch runs nothing
+    pub fn apply_commands(&mut self, bytes: &[u8]) -> Result<Vec<i32>, String> {
+        batch::apply_commands(self, bytes)
     }
 
     /// Clear all particles
     pub fn clear(&mut self) {
-        commands::clear(self)
+        self.record(|| RecordedCommand::Clear);
+        history::recorded(self, commands::clear)
+    }
+
+    // === STAMP API ===
+
+    /// Capture a `w` x `h` region (types, life, temperature, velocity) as a stamp
+    pub fn copy_region(&self, x: i32, y: i32, w: u32, h: u32) -> Result<Stamp, String> {
+        stamp::place::copy_region(self, x, y, w, h)
+    }
+
+    /// Capture a region and remove its particles
+    pub fn cut_region(&mut self, x: i32, y: i32, w: u32, h: u32) -> Result<Stamp, String> {
+        self.record(|| RecordedCommand::CutRegion { x, y, w, h });
+        history::recorded(self, |world| stamp::place::cut_region(world, x, y, w, h))
+    }
+
+    /// Paste a stamp with its top-left corner at (x, y)
+    /// The stamp is mirrored horizontally (optional), then rotated clockwise by 90° steps.
+    /// Returns the number of particles placed
+    pub fn paste_stamp(&mut self, stamp: &Stamp, x: i32, y: i32, quarter_turns: u8, mirror: bool, mode: StampBlend) -> u32 {
+        if self.is_recording() {
+            let bytes = stamp.to_bytes(&self.content);
+            self.record(|| RecordedCommand::PasteStamp { bytes, x, y, quarter_turns, mirror, mode: mode.code() });
+        }
+        history::recorded(self, |world| stamp::place::paste_stamp(world, stamp, x, y, quarter_turns, mirror, mode))
+    }
+
+    /// Serialize a stamp (with the element key table) for sharing
+    pub fn encode_stamp(&self, stamp: &Stamp) -> Vec<u8> {
+        stamp.to_bytes(&self.content)
+    }
+
+    /// Decode a shared stamp; elements missing from the current content become empty
+    pub fn decode_stamp(&self, bytes: &[u8]) -> Result<Stamp, String> {
+        Stamp::from_bytes(bytes, &self.content)
+    }
+
+    /// Convert an RGBA image into particles by nearest element color
+    /// Transparent pixels (alpha below the threshold) become empty cells.
+    /// Returns the number of particles placed
+    pub fn import_image(&mut self, rgba: &[u8], width: u32, height: u32, x: i32, y: i32, options: &ImageImportOptions) -> Result<u32, String> {
+        self.record(|| RecordedCommand::ImportImage {
+            rgba: rgba.to_vec(),
+            width,
+            height,
+            x,
+            y,
+            palette: options.palette().map(<[ElementId]>::to_vec).unwrap_or_default(),
+            alpha_threshold: options.alpha_threshold(),
+        });
+        history::recorded(self, |world| image_import::import_image(world, rgba, width, height, x, y, options))
+    }
+
+    // === UNDO/REDO API ===
+
+    /// Start grouping edits into one undo step (e.g. on pointer down)
+    pub fn begin_stroke(&mut self) {
+        self.record(|| RecordedCommand::BeginStroke);
+        self.history.begin_stroke();
+    }
+
+    /// Close the current undo step (e.g. on pointer up)
+    pub fn end_stroke(&mut self) {
+        self.record(|| RecordedCommand::EndStroke);
+        self.history.end_stroke();
+    }
+
+    /// Revert the last undo step. Returns false when there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        self.record(|| RecordedCommand::Undo);
+        history::undo(self)
+    }
+
+    /// Re-apply the last undone step. Returns false when there is nothing to redo
+    pub fn redo(&mut self) -> bool {
+        self.record(|| RecordedCommand::Redo);
+        history::redo(self)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Memory budget for undo + redo records; oldest steps are dropped first.
+    /// A single step larger than the budget (e.g. `clear` on a big world) is not undoable.
+    pub fn set_history_budget(&mut self, bytes: usize) {
+        self.record(|| RecordedCommand::SetHistoryBudget { bytes: bytes as u64 });
+        self.history.set_budget(bytes);
+    }
+
+    pub fn clear_history(&mut self) {
+        self.record(|| RecordedCommand::ClearHistory);
+        self.history.clear();
+    }
+
+    // === SNAPSHOT API ===
+
+    /// Serialize the whole world (grid, rigid bodies, settings) into a versioned binary snapshot
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        snapshot::save_snapshot(self)
+    }
+
+    /// Restore a world saved with `save_snapshot`
+    /// Element ids are remapped by key against the current content; cells whose
+    /// element no longer exists are emptied and reported.
+    /// The world is left untouched if the snapshot is invalid
+    pub fn load_snapshot(&mut self, bytes: &[u8]) -> Result<SnapshotLoadReport, String> {
+        self.record(|| RecordedCommand::LoadSnapshot { bytes: bytes.to_vec() });
+        let report = snapshot::load_snapshot(self, bytes)?;
+        self.history.clear();
+        Ok(report)
     }
     
+    /// Replace the world with a JSON scene (size, settings, shapes, rigid bodies).
+    /// Element keys resolve against the active content; an invalid scene changes nothing.
+    /// Clears undo history
+    pub fn load_scene(&mut self, json: &str) -> Result<SceneLoadReport, String> {
+        self.record(|| RecordedCommand::LoadScene { json: json.to_string() });
+        scene::load_scene(self, json)
+    }
+
+    // === STATE HASHING ===
+
+    /// Hash of grid arrays, rigid bodies and RNG state (compare runs/clients cheaply)
+    pub fn state_hash(&self) -> u64 {
+        state_hash::state_hash(self)
+    }
+
+    /// Per-chunk grid hashes, row-major (`cy * chunks_x + cx`), to locate a divergence
+    pub fn chunk_hashes(&self) -> Vec<u64> {
+        state_hash::chunk_hashes(self)
+    }
+
+    // === RECORDING API ===
+
+    /// Start logging every mutating call (with its frame) for deterministic replay
+    /// Restarts the recording if one is running; clears undo history
+    pub fn start_recording(&mut self) {
+        replay::start_recording(self)
+    }
+
+    /// Stop logging and return the encoded recording (`None` if not recording)
+    pub fn stop_recording(&mut self) -> Option<Vec<u8>> {
+        replay::stop_recording(self)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Re-run a recording on this world and verify the final state matches
+    /// The world must have the same content loaded as the recorded one. Its previous
+    /// state is replaced by the recording's start snapshot
+    pub fn replay_recording(&mut self, bytes: &[u8]) -> Result<ReplayReport, String> {
+        replay::replay_recording(self, bytes)
+    }
+
     // === RIGID BODY API ===
     
     /// Spawn a rectangular rigid body at position (x, y) with size (w, h)
     /// Returns the body ID
-    pub fn spawn_rigid_body(&mut self, x: f32, y: f32, w: i32, h: i32, element_id: u8) -> u32 {
+    pub fn spawn_rigid_body(&mut self, x: f32, y: f32, w: i32, h: i32, element_id: ElementId) -> u32 {
+        self.record(|| RecordedCommand::SpawnRigidBody { x, y, w, h, element: element_id });
         rigid::spawn_rigid_body(self, x, y, w, h, element_id)
     }
     
     /// Spawn a circular rigid body at position (x, y) with given radius
     /// Returns the body ID
-    pub fn spawn_rigid_circle(&mut self, x: f32, y: f32, radius: i32, element_id: u8) -> u32 {
+    pub fn spawn_rigid_circle(&mut self, x: f32, y: f32, radius: i32, element_id: ElementId) -> u32 {
+        self.record(|| RecordedCommand::SpawnRigidCircle { x, y, radius, element: element_id });
         rigid::spawn_rigid_circle(self, x, y, radius, element_id)
     }
     
     /// Remove a rigid body by ID
     pub fn remove_rigid_body(&mut self, id: u32) {
+        self.record(|| RecordedCommand::RemoveRigidBody { id });
         rigid::remove_rigid_body(self, id)
     }
     
@@ -203,6 +533,7 @@ impl WorldCore {
 
     /// Step the simulation forward
     pub fn step(&mut self) {
+        self.record(|| RecordedCommand::Step { count: 1 });
         step::step(self);
     }
     
@@ -232,7 +563,7 @@ impl WorldCore {
     }
 
     /// Get pointer to types array (for JS rendering)
-    pub fn types_ptr(&self) -> *const u8 {
+    pub fn types_ptr(&self) -> *const ElementId {
         self.grid.types_ptr()
     }
 
@@ -264,7 +595,7 @@ impl WorldCore {
     }
 
     pub fn types_byte_len(&self) -> usize {
-        self.grid.size()
+        self.grid.size() * std::mem::size_of::<ElementId>()
     }
 
     pub fn colors_byte_len(&self) -> usize {
@@ -285,6 +616,21 @@ impl WorldCore {
         self.grid.size()
     }
 
+    /// Element, temperature, life, velocity and rigid body of one cell (`None` outside the world)
+    pub fn inspect_cell(&self, x: i32, y: i32) -> Option<CellInfo> {
+        inspect::inspect_cell(self, x, y)
+    }
+
+    /// Element histogram and temperature range of a rectangle, clipped to the world
+    pub fn region_stats(&self, x: i32, y: i32, w: u32, h: u32) -> Result<RegionStats, String> {
+        inspect::region_stats(self, x, y, w, h)
+    }
+
+    /// Render the grid into an RGBA8 buffer (`width * height * 4` bytes)
+    pub fn render_rgba(&self, view: RenderView) -> Vec<u8> {
+        render::render_rgba(self, view)
+    }
+
     /// Get chunks X count (for JS coordinate calculation)
     pub fn chunks_x(&self) -> u32 {
         self.chunks.dimensions().0
@@ -300,6 +646,7 @@ impl WorldCore {
             types_ptr: self.types_ptr(),
             types_len_elements: self.types_len(),
             types_len_bytes: self.types_byte_len(),
+            types_element_bytes: std::mem::size_of::<ElementId>(),
             colors_ptr: self.colors_ptr(),
             colors_len_elements: self.colors_len_elements(),
             colors_len_bytes: self.colors_len_bytes(),
@@ -316,6 +663,14 @@ mod tests;
 
 // Private simulation methods
 impl WorldCore {
+    /// Log a mutating call while recording (the command is only built when needed)
+    #[inline]
+    fn record(&mut self, cmd: impl FnOnce() -> RecordedCommand) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.push(self.frame, cmd());
+        }
+    }
+
     /// Process a row of chunks
     fn process_chunk_row(&mut self, cy: u32, chunks_x: u32, go_right: bool) {
         chunk_processing::process_chunk_row(self, cy, chunks_x, go_right);
@@ -0,0 +1,134 @@
+//! Headless RGBA renderer (thumbnails, native tests, server-side previews).
+//!
+//! Output is row-major RGBA8, `width * height * 4` bytes, ready for `ImageData`
+//! or any image encoder.
+
+use crate::elements::{CategoryId, CAT_BIO, CAT_ENERGY, CAT_GAS, CAT_LIQUID, CAT_POWDER, CAT_SOLID, CAT_UTILITY, EL_EMPTY};
+
+use super::WorldCore;
+
+/// Background for empty cells in non-color views (matches `BG_COLOR`)
+const BG_RGBA: [u8; 4] = [10, 10, 10, 255];
+
+/// Speed mapped to full brightness in the velocity view (pixels/frame)
+const VELOCITY_FULL_SCALE: f32 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderView {
+    /// The `colors` array as-is
+    Normal,
+    /// Temperature gradient (same scale as the web thermal view)
+    Thermal,
+    /// Hue = direction, brightness = speed
+    Velocity,
+    /// One flat color per element category
+    Category,
+}
+
+impl RenderView {
+    /// JS code: 0 = normal, 1 = thermal, 2 = velocity, 3 = category
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Normal),
+            1 => Some(Self::Thermal),
+            2 => Some(Self::Velocity),
+            3 => Some(Self::Category),
+            _ => None,
+        }
+    }
+}
+
+pub(super) fn render_rgba(world: &WorldCore, view: RenderView) -> Vec<u8> {
+    let grid = &world.grid;
+    let mut out = vec![0u8; grid.size() * 4];
+
+    for (idx, px) in out.chunks_exact_mut(4).enumerate() {
+        let element = grid.types[idx];
+        let rgba = match view {
+            // ABGR u32 is RGBA in little-endian byte order
+            RenderView::Normal => grid.colors[idx].to_le_bytes(),
+            RenderView::Thermal => {
+                // Empty cells show the ambient air like the web renderer
+                let t = if element == EL_EMPTY {
+                    world.ambient_temperature
+                } else {
+                    grid.temperature[idx]
+                };
+                thermal_color(t)
+            }
+            RenderView::Velocity if element == EL_EMPTY => BG_RGBA,
+            RenderView::Velocity => velocity_color(grid.vx[idx], grid.vy[idx]),
+            RenderView::Category if element == EL_EMPTY => BG_RGBA,
+            RenderView::Category => match world.content.props(element) {
+                Some(props) => category_color(props.category),
+                None => BG_RGBA,
+            },
+        };
+        px.copy_from_slice(&rgba);
+    }
+    out
+}
+
+/// Blue (-20) -> Cyan (0) -> Green (20) -> Yellow (100) -> Red (500) -> White (1000)
+fn thermal_color(t: f32) -> [u8; 4] {
+    let (r, g, b) = if t < 0.0 {
+        let intensity = (t.abs() / 30.0).min(1.0);
+        (0.0, 0.0, 128.0 + 127.0 * intensity)
+    } else if t < 20.0 {
+        (0.0, t / 20.0 * 255.0, 255.0)
+    } else if t < 50.0 {
+        (0.0, 255.0, 255.0 * (1.0 - (t - 20.0) / 30.0))
+    } else if t < 100.0 {
+        (255.0 * (t - 50.0) / 50.0, 255.0, 0.0)
+    } else if t < 500.0 {
+        (255.0, 255.0 * (1.0 - (t - 100.0) / 400.0), 0.0)
+    } else {
+        let ratio = ((t - 500.0) / 500.0).min(1.0);
+        (255.0, 255.0 * ratio, 255.0 * ratio)
+    };
+    [r as u8, g as u8, b as u8, 255]
+}
+
+fn velocity_color(vx: f32, vy: f32) -> [u8; 4] {
+    let speed = (vx * vx + vy * vy).sqrt();
+    if speed <= f32::EPSILON {
+        return [0, 0, 0, 255];
+    }
+    let value = (speed / VELOCITY_FULL_SCALE).min(1.0);
+    let hue = (vy.atan2(vx).to_degrees() + 360.0) % 360.0;
+    let [r, g, b] = hsv_to_rgb(hue, 1.0, value);
+    [r, g, b, 255]
+}
+
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [u8; 3] {
+    let c = v * s;
+    let hp = h / 60.0;
+    let x = c * (1.0 - (hp % 2.0 - 1.0).abs());
+    let (r, g, b) = match hp as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    [
+        ((r + m) * 255.0).round() as u8,
+        ((g + m) * 255.0).round() as u8,
+        ((b + m) * 255.0).round() as u8,
+    ]
+}
+
+fn category_color(category: CategoryId) -> [u8; 4] {
+    match category {
+        CAT_SOLID => [136, 136, 136, 255],
+        CAT_POWDER => [230, 200, 90, 255],
+        CAT_LIQUID => [50, 110, 240, 255],
+        CAT_GAS => [200, 200, 230, 255],
+        CAT_ENERGY => [255, 110, 20, 255],
+        CAT_UTILITY => [220, 60, 220, 255],
+        CAT_BIO => [60, 200, 80, 255],
+        _ => [255, 255, 255, 255],
+    }
+}
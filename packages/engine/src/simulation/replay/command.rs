@@ -0,0 +1,534 @@
+//! Recorded world commands and their binary encoding.
+//!
+//! Element ids (and brush targets) use the reader's element id width
+//! (`ByteReader::set_element_bytes`), which defaults to this build's width.
+
+use crate::elements::ElementId;
+use crate::simulation::brush::BrushShape;
+use crate::simulation::terrain::TerrainConfig;
+use crate::simulation::snapshot::codec::{ByteReader, ByteWriter};
+
+/// One mutating `WorldCore` call, with its arguments
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum RecordedCommand {
+    /// `count` consecutive `step()` calls
+    Step { count: u32 },
+    AddParticle { x: u32, y: u32, element: ElementId },
+    AddParticlesInRadius { cx: i32, cy: i32, radius: i32, element: ElementId },
+    RemoveParticle { x: u32, y: u32 },
+    RemoveParticlesInRadius { cx: i32, cy: i32, radius: i32 },
+    Clear,
+    SetGravity { x: f32, y: f32 },
+    SetAmbientTemperature { temp: f32 },
+    SpawnRigidBody { x: f32, y: f32, w: i32, h: i32, element: ElementId },
+    SpawnRigidCircle { x: f32, y: f32, radius: i32, element: ElementId },
+    RemoveRigidBody { id: u32 },
+    BeginStroke,
+    EndStroke,
+    Undo,
+    Redo,
+    SetHistoryBudget { bytes: u64 },
+    ClearHistory,
+    LoadSnapshot { bytes: Vec<u8> },
+    LoadContentBundle { json: String },
+    ReloadContentBundle { json: String, fallback_key: Option<String> },
+    SetSeed { seed: u64 },
+    Resize { width: u32, height: u32, anchor: u8 },
+    CutRegion { x: i32, y: i32, w: u32, h: u32 },
+    PasteStamp { bytes: Vec<u8>, x: i32, y: i32, quarter_turns: u8, mirror: bool, mode: u8 },
+    /// Empty `palette` = all visible elements
+    ImportImage { rgba: Vec<u8>, width: u32, height: u32, x: i32, y: i32, palette: Vec<ElementId>, alpha_threshold: u8 },
+    /// `mode` / `target` as in `BrushMode::from_code`
+    Paint { shape: BrushShape, element: ElementId, mode: u8, target: ElementId },
+    FloodFill { x: u32, y: u32, element: ElementId, connectivity: u8, max_cells: u32 },
+    ApplyHeatInRadius { cx: i32, cy: i32, radius: i32, delta: f32, falloff: bool },
+    SetTemperatureInRadius { cx: i32, cy: i32, radius: i32, temp: f32 },
+    ApplyImpulse { cx: i32, cy: i32, radius: i32, vx: f32, vy: f32 },
+    ApplyBlast { cx: i32, cy: i32, radius: i32, strength: f32 },
+    ApplyVortex { cx: i32, cy: i32, radius: i32, strength: f32 },
+    GenerateTerrain { config: TerrainConfig },
+    LoadScene { json: String },
+    SetElementProperty { element: ElementId, field: String, value: f64 },
+    ResetElementProperties,
+}
+
+// Wire tags: never reuse or renumber, append new ones.
+const TAG_STEP: u8 = 0;
+const TAG_ADD_PARTICLE: u8 = 1;
+const TAG_ADD_RADIUS: u8 = 2;
+const TAG_REMOVE_PARTICLE: u8 = 3;
+const TAG_REMOVE_RADIUS: u8 = 4;
+const TAG_CLEAR: u8 = 5;
+const TAG_SET_GRAVITY: u8 = 6;
+const TAG_SET_AMBIENT: u8 = 7;
+const TAG_SPAWN_BODY: u8 = 8;
+const TAG_SPAWN_CIRCLE: u8 = 9;
+const TAG_REMOVE_BODY: u8 = 10;
+const TAG_BEGIN_STROKE: u8 = 11;
+const TAG_END_STROKE: u8 = 12;
+const TAG_UNDO: u8 = 13;
+const TAG_REDO: u8 = 14;
+const TAG_HISTORY_BUDGET: u8 = 15;
+const TAG_CLEAR_HISTORY: u8 = 16;
+const TAG_LOAD_SNAPSHOT: u8 = 17;
+const TAG_LOAD_CONTENT: u8 = 18;
+const TAG_RELOAD_CONTENT: u8 = 19;
+const TAG_SET_SEED: u8 = 20;
+const TAG_RESIZE: u8 = 21;
+const TAG_CUT_REGION: u8 = 22;
+const TAG_PASTE_STAMP: u8 = 23;
+const TAG_IMPORT_IMAGE: u8 = 24;
+const TAG_PAINT: u8 = 25;
+const TAG_FLOOD_FILL: u8 = 26;
+const TAG_APPLY_HEAT: u8 = 27;
+const TAG_SET_TEMPERATURE: u8 = 28;
+const TAG_APPLY_IMPULSE: u8 = 29;
+const TAG_APPLY_BLAST: u8 = 30;
+const TAG_APPLY_VORTEX: u8 = 31;
+const TAG_GENERATE_TERRAIN: u8 = 32;
+const TAG_LOAD_SCENE: u8 = 33;
+const TAG_SET_ELEMENT_PROPERTY: u8 = 34;
+const TAG_RESET_ELEMENT_PROPERTIES: u8 = 35;
+
+// Brush shape sub-tags inside TAG_PAINT
+const SHAPE_SQUARE: u8 = 0;
+const SHAPE_LINE: u8 = 1;
+const SHAPE_RING: u8 = 2;
+const SHAPE_POLYGON: u8 = 3;
+
+impl RecordedCommand {
+    pub(crate) fn encode(&self, w: &mut ByteWriter) {
+        match self {
+            Self::Step { count } => {
+                w.put_u8(TAG_STEP);
+                w.put_u32(*count);
+            }
+            Self::AddParticle { x, y, element } => {
+                w.put_u8(TAG_ADD_PARTICLE);
+                w.put_u32(*x);
+                w.put_u32(*y);
+                w.put_element(*element);
+            }
+            Self::AddParticlesInRadius { cx, cy, radius, element } => {
+                w.put_u8(TAG_ADD_RADIUS);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+                w.put_element(*element);
+            }
+            Self::RemoveParticle { x, y } => {
+                w.put_u8(TAG_REMOVE_PARTICLE);
+                w.put_u32(*x);
+                w.put_u32(*y);
+            }
+            Self::RemoveParticlesInRadius { cx, cy, radius } => {
+                w.put_u8(TAG_REMOVE_RADIUS);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+            }
+            Self::Clear => w.put_u8(TAG_CLEAR),
+            Self::SetGravity { x, y } => {
+                w.put_u8(TAG_SET_GRAVITY);
+                w.put_f32(*x);
+                w.put_f32(*y);
+            }
+            Self::SetAmbientTemperature { temp } => {
+                w.put_u8(TAG_SET_AMBIENT);
+                w.put_f32(*temp);
+            }
+            Self::SpawnRigidBody { x, y, w: bw, h, element } => {
+                w.put_u8(TAG_SPAWN_BODY);
+                w.put_f32(*x);
+                w.put_f32(*y);
+                w.put_i32(*bw);
+                w.put_i32(*h);
+                w.put_element(*element);
+            }
+            Self::SpawnRigidCircle { x, y, radius, element } => {
+                w.put_u8(TAG_SPAWN_CIRCLE);
+                w.put_f32(*x);
+                w.put_f32(*y);
+                w.put_i32(*radius);
+                w.put_element(*element);
+            }
+            Self::RemoveRigidBody { id } => {
+                w.put_u8(TAG_REMOVE_BODY);
+                w.put_u32(*id);
+            }
+            Self::BeginStroke => w.put_u8(TAG_BEGIN_STROKE),
+            Self::EndStroke => w.put_u8(TAG_END_STROKE),
+            Self::Undo => w.put_u8(TAG_UNDO),
+            Self::Redo => w.put_u8(TAG_REDO),
+            Self::SetHistoryBudget { bytes } => {
+                w.put_u8(TAG_HISTORY_BUDGET);
+                w.put_u64(*bytes);
+            }
+            Self::ClearHistory => w.put_u8(TAG_CLEAR_HISTORY),
+            Self::LoadSnapshot { bytes } => {
+                w.put_u8(TAG_LOAD_SNAPSHOT);
+                w.put_u32(bytes.len() as u32);
+                w.put_bytes(bytes);
+            }
+            Self::LoadContentBundle { json } => {
+                w.put_u8(TAG_LOAD_CONTENT);
+                w.put_str(json);
+            }
+            Self::ReloadContentBundle { json, fallback_key } => {
+                w.put_u8(TAG_RELOAD_CONTENT);
+                w.put_str(json);
+                w.put_u8(fallback_key.is_some() as u8);
+                if let Some(key) = fallback_key {
+                    w.put_str(key);
+                }
+            }
+            Self::SetSeed { seed } => {
+                w.put_u8(TAG_SET_SEED);
+                w.put_u64(*seed);
+            }
+            Self::Resize { width, height, anchor } => {
+                w.put_u8(TAG_RESIZE);
+                w.put_u32(*width);
+                w.put_u32(*height);
+                w.put_u8(*anchor);
+            }
+            Self::CutRegion { x, y, w: rw, h } => {
+                w.put_u8(TAG_CUT_REGION);
+                w.put_i32(*x);
+                w.put_i32(*y);
+                w.put_u32(*rw);
+                w.put_u32(*h);
+            }
+            Self::PasteStamp { bytes, x, y, quarter_turns, mirror, mode } => {
+                w.put_u8(TAG_PASTE_STAMP);
+                w.put_u32(bytes.len() as u32);
+                w.put_bytes(bytes);
+                w.put_i32(*x);
+                w.put_i32(*y);
+                w.put_u8(*quarter_turns);
+                w.put_u8(*mirror as u8);
+                w.put_u8(*mode);
+            }
+            Self::ImportImage { rgba, width, height, x, y, palette, alpha_threshold } => {
+                w.put_u8(TAG_IMPORT_IMAGE);
+                w.put_u32(rgba.len() as u32);
+                w.put_bytes(rgba);
+                w.put_u32(*width);
+                w.put_u32(*height);
+                w.put_i32(*x);
+                w.put_i32(*y);
+                w.put_u32(palette.len() as u32);
+                w.put_element_slice(palette);
+                w.put_u8(*alpha_threshold);
+            }
+            Self::Paint { shape, element, mode, target } => {
+                w.put_u8(TAG_PAINT);
+                encode_shape(w, shape);
+                w.put_element(*element);
+                w.put_u8(*mode);
+                w.put_element(*target);
+            }
+            Self::FloodFill { x, y, element, connectivity, max_cells } => {
+                w.put_u8(TAG_FLOOD_FILL);
+                w.put_u32(*x);
+                w.put_u32(*y);
+                w.put_element(*element);
+                w.put_u8(*connectivity);
+                w.put_u32(*max_cells);
+            }
+            Self::ApplyHeatInRadius { cx, cy, radius, delta, falloff } => {
+                w.put_u8(TAG_APPLY_HEAT);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+                w.put_f32(*delta);
+                w.put_u8(*falloff as u8);
+            }
+            Self::SetTemperatureInRadius { cx, cy, radius, temp } => {
+                w.put_u8(TAG_SET_TEMPERATURE);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+                w.put_f32(*temp);
+            }
+            Self::ApplyImpulse { cx, cy, radius, vx, vy } => {
+                w.put_u8(TAG_APPLY_IMPULSE);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+                w.put_f32(*vx);
+                w.put_f32(*vy);
+            }
+            Self::ApplyBlast { cx, cy, radius, strength } => {
+                w.put_u8(TAG_APPLY_BLAST);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+                w.put_f32(*strength);
+            }
+            Self::ApplyVortex { cx, cy, radius, strength } => {
+                w.put_u8(TAG_APPLY_VORTEX);
+                w.put_i32(*cx);
+                w.put_i32(*cy);
+                w.put_i32(*radius);
+                w.put_f32(*strength);
+            }
+            Self::GenerateTerrain { config } => {
+                w.put_u8(TAG_GENERATE_TERRAIN);
+                encode_terrain(w, config);
+            }
+            Self::LoadScene { json } => {
+                w.put_u8(TAG_LOAD_SCENE);
+                w.put_str(json);
+            }
+            Self::SetElementProperty { element, field, value } => {
+                w.put_u8(TAG_SET_ELEMENT_PROPERTY);
+                w.put_element(*element);
+                w.put_str(field);
+                w.put_u64(value.to_bits());
+            }
+            Self::ResetElementProperties => w.put_u8(TAG_RESET_ELEMENT_PROPERTIES),
+        }
+    }
+
+    pub(crate) fn decode(r: &mut ByteReader) -> Result<Self, String> {
+        let tag = r.u8()?;
+        let cmd = match tag {
+            TAG_STEP => Self::Step { count: r.u32()? },
+            TAG_ADD_PARTICLE => Self::AddParticle { x: r.u32()?, y: r.u32()?, element: r.element()? },
+            TAG_ADD_RADIUS => Self::AddParticlesInRadius {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+                element: r.element()?,
+            },
+            TAG_REMOVE_PARTICLE => Self::RemoveParticle { x: r.u32()?, y: r.u32()? },
+            TAG_REMOVE_RADIUS => Self::RemoveParticlesInRadius {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+            },
+            TAG_CLEAR => Self::Clear,
+            TAG_SET_GRAVITY => Self::SetGravity { x: r.f32()?, y: r.f32()? },
+            TAG_SET_AMBIENT => Self::SetAmbientTemperature { temp: r.f32()? },
+            TAG_SPAWN_BODY => Self::SpawnRigidBody {
+                x: r.f32()?,
+                y: r.f32()?,
+                w: r.i32()?,
+                h: r.i32()?,
+                element: r.element()?,
+            },
+            TAG_SPAWN_CIRCLE => Self::SpawnRigidCircle {
+                x: r.f32()?,
+                y: r.f32()?,
+                radius: r.i32()?,
+                element: r.element()?,
+            },
+            TAG_REMOVE_BODY => Self::RemoveRigidBody { id: r.u32()? },
+            TAG_BEGIN_STROKE => Self::BeginStroke,
+            TAG_END_STROKE => Self::EndStroke,
+            TAG_UNDO => Self::Undo,
+            TAG_REDO => Self::Redo,
+            TAG_HISTORY_BUDGET => Self::SetHistoryBudget { bytes: r.u64()? },
+            TAG_CLEAR_HISTORY => Self::ClearHistory,
+            TAG_LOAD_SNAPSHOT => {
+                let len = r.count(1)?;
+                Self::LoadSnapshot { bytes: r.take(len)?.to_vec() }
+            }
+            TAG_LOAD_CONTENT => Self::LoadContentBundle { json: r.string()? },
+            TAG_RELOAD_CONTENT => {
+                let json = r.string()?;
+                let fallback_key = if r.u8()? != 0 { Some(r.string()?) } else { None };
+                Self::ReloadContentBundle { json, fallback_key }
+            }
+            TAG_SET_SEED => Self::SetSeed { seed: r.u64()? },
+            TAG_RESIZE => Self::Resize { width: r.u32()?, height: r.u32()?, anchor: r.u8()? },
+            TAG_CUT_REGION => Self::CutRegion { x: r.i32()?, y: r.i32()?, w: r.u32()?, h: r.u32()? },
+            TAG_PASTE_STAMP => {
+                let len = r.count(1)?;
+                Self::PasteStamp {
+                    bytes: r.take(len)?.to_vec(),
+                    x: r.i32()?,
+                    y: r.i32()?,
+                    quarter_turns: r.u8()?,
+                    mirror: r.u8()? != 0,
+                    mode: r.u8()?,
+                }
+            }
+            TAG_IMPORT_IMAGE => {
+                let len = r.count(1)?;
+                let rgba = r.take(len)?.to_vec();
+                let (width, height, x, y) = (r.u32()?, r.u32()?, r.i32()?, r.i32()?);
+                let mut palette = vec![0; r.count(r.element_bytes() as usize)?];
+                r.element_into(&mut palette)?;
+                Self::ImportImage {
+                    rgba,
+                    width,
+                    height,
+                    x,
+                    y,
+                    palette,
+                    alpha_threshold: r.u8()?,
+                }
+            }
+            TAG_PAINT => Self::Paint {
+                shape: decode_shape(r)?,
+                element: r.element()?,
+                mode: r.u8()?,
+                target: r.element()?,
+            },
+            TAG_FLOOD_FILL => Self::FloodFill {
+                x: r.u32()?,
+                y: r.u32()?,
+                element: r.element()?,
+                connectivity: r.u8()?,
+                max_cells: r.u32()?,
+            },
+            TAG_APPLY_HEAT => Self::ApplyHeatInRadius {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+                delta: r.f32()?,
+                falloff: r.u8()? != 0,
+            },
+            TAG_SET_TEMPERATURE => Self::SetTemperatureInRadius {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+                temp: r.f32()?,
+            },
+            TAG_APPLY_IMPULSE => Self::ApplyImpulse {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+                vx: r.f32()?,
+                vy: r.f32()?,
+            },
+            TAG_APPLY_BLAST => Self::ApplyBlast {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+                strength: r.f32()?,
+            },
+            TAG_APPLY_VORTEX => Self::ApplyVortex {
+                cx: r.i32()?,
+                cy: r.i32()?,
+                radius: r.i32()?,
+                strength: r.f32()?,
+            },
+            TAG_GENERATE_TERRAIN => Self::GenerateTerrain { config: decode_terrain(r)? },
+            TAG_LOAD_SCENE => Self::LoadScene { json: r.string()? },
+            TAG_SET_ELEMENT_PROPERTY => Self::SetElementProperty {
+                element: r.element()?,
+                field: r.string()?,
+                value: f64::from_bits(r.u64()?),
+            },
+            TAG_RESET_ELEMENT_PROPERTIES => Self::ResetElementProperties,
+            other => return Err(format!("unknown recorded command tag {}", other)),
+        };
+        Ok(cmd)
+    }
+}
+
+fn encode_shape(w: &mut ByteWriter, shape: &BrushShape) {
+    match shape {
+        BrushShape::Square { cx, cy, half_size } => {
+            w.put_u8(SHAPE_SQUARE);
+            w.put_i32(*cx);
+            w.put_i32(*cy);
+            w.put_i32(*half_size);
+        }
+        BrushShape::Line { x0, y0, x1, y1, thickness } => {
+            w.put_u8(SHAPE_LINE);
+            w.put_i32(*x0);
+            w.put_i32(*y0);
+            w.put_i32(*x1);
+            w.put_i32(*y1);
+            w.put_u32(*thickness);
+        }
+        BrushShape::Ring { cx, cy, radius, thickness } => {
+            w.put_u8(SHAPE_RING);
+            w.put_i32(*cx);
+            w.put_i32(*cy);
+            w.put_i32(*radius);
+            w.put_u32(*thickness);
+        }
+        BrushShape::Polygon { points } => {
+            w.put_u8(SHAPE_POLYGON);
+            w.put_u32(points.len() as u32);
+            for &(x, y) in points {
+                w.put_i32(x);
+                w.put_i32(y);
+            }
+        }
+    }
+}
+
+fn decode_shape(r: &mut ByteReader) -> Result<BrushShape, String> {
+    let shape = match r.u8()? {
+        SHAPE_SQUARE => BrushShape::Square { cx: r.i32()?, cy: r.i32()?, half_size: r.i32()? },
+        SHAPE_LINE => BrushShape::Line {
+            x0: r.i32()?,
+            y0: r.i32()?,
+            x1: r.i32()?,
+            y1: r.i32()?,
+            thickness: r.u32()?,
+        },
+        SHAPE_RING => BrushShape::Ring {
+            cx: r.i32()?,
+            cy: r.i32()?,
+            radius: r.i32()?,
+            thickness: r.u32()?,
+        },
+        SHAPE_POLYGON => {
+            let count = r.count(8)?;
+            let mut points = Vec::with_capacity(count);
+            for _ in 0..count {
+                points.push((r.i32()?, r.i32()?));
+            }
+            BrushShape::Polygon { points }
+        }
+        other => return Err(format!("unknown brush shape tag {}", other)),
+    };
+    Ok(shape)
+}
+
+fn encode_terrain(w: &mut ByteWriter, c: &TerrainConfig) {
+    w.put_u64(c.seed);
+    w.put_f32(c.surface_level);
+    w.put_f32(c.surface_amplitude);
+    w.put_u32(c.dirt_depth);
+    w.put_f32(c.strata);
+    w.put_f32(c.cave_density);
+    w.put_u32(c.water_pools);
+    w.put_u32(c.lava_pockets);
+    w.put_f32(c.vegetation);
+    w.put_element_slice(&[
+        c.stone_element,
+        c.dirt_element,
+        c.water_element,
+        c.lava_element,
+        c.seed_element,
+        c.plant_element,
+    ]);
+}
+
+fn decode_terrain(r: &mut ByteReader) -> Result<TerrainConfig, String> {
+    Ok(TerrainConfig {
+        seed: r.u64()?,
+        surface_level: r.f32()?,
+        surface_amplitude: r.f32()?,
+        dirt_depth: r.u32()?,
+        strata: r.f32()?,
+        cave_density: r.f32()?,
+        water_pools: r.u32()?,
+        lava_pockets: r.u32()?,
+        vegetation: r.f32()?,
+        stone_element: r.element()?,
+        dirt_element: r.element()?,
+        water_element: r.element()?,
+        lava_element: r.element()?,
+        seed_element: r.element()?,
+        plant_element: r.element()?,
+    })
+}
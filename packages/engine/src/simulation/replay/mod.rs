@@ -0,0 +1,248 @@
+//! Deterministic input recording and replay.
+//!
+//! A recording is the world snapshot taken when recording started (grid, bodies,
+//! settings and RNG streams) followed by every mutating call, each tagged with
+//! the frame it was applied on, and a hash of the final state. Replaying loads the
+//! start snapshot, re-applies the log and checks the result against that hash.
+//!
+//! Layout (little-endian):
+//! - header: magic `PREC`, format version (u16), then reserved (u16) in v1 and
+//!   element id width in bytes (u16) from v2 (v1 ids are one byte)
+//! - start snapshot: byte length (u32) + `save_snapshot()` bytes
+//! - commands: count (u32), then `(frame: u64, command)` entries
+//! - final frame (u64), final state hash (u64)
+//!
+//! Content bundles are not embedded: the replaying world must have the same
+//! content loaded that the recording world had when recording started.
+
+pub(crate) mod command;
+
+use self::command::RecordedCommand;
+use super::snapshot::codec::{ByteReader, ByteWriter, ELEMENT_ID_BYTES};
+use super::state_hash::state_hash;
+use super::{Brush, BrushMode, ImageImportOptions, ResizeAnchor, StampBlend, WorldCore};
+
+pub(crate) const RECORDING_MAGIC: [u8; 4] = *b"PREC";
+pub(crate) const RECORDING_VERSION: u16 = 2;
+
+/// Commands captured since `start_recording`
+pub(crate) struct InputRecorder {
+    start_snapshot: Vec<u8>,
+    entries: Vec<(u64, RecordedCommand)>,
+}
+
+impl InputRecorder {
+    pub(crate) fn push(&mut self, frame: u64, cmd: RecordedCommand) {
+        // Runs of steps collapse into one entry.
+        if let RecordedCommand::Step { count: 1 } = cmd {
+            if let Some((_, RecordedCommand::Step { count })) = self.entries.last_mut() {
+                *count += 1;
+                return;
+            }
+        }
+        self.entries.push((frame, cmd));
+    }
+}
+
+/// Outcome of a successful replay
+#[derive(Clone, Debug, Default)]
+pub struct ReplayReport {
+    pub commands: u32,
+    pub final_frame: u64,
+    pub state_hash: u64,
+}
+
+pub(super) fn start_recording(world: &mut WorldCore) {
+    // Undo must not reach past the start of the recording.
+    world.history.clear();
+    world.recorder = Some(InputRecorder {
+        start_snapshot: super::snapshot::save_snapshot(world),
+        entries: Vec::new(),
+    });
+}
+
+pub(super) fn stop_recording(world: &mut WorldCore) -> Option<Vec<u8>> {
+    let recorder = world.recorder.take()?;
+
+    let mut w = ByteWriter::with_capacity(recorder.start_snapshot.len() + 64 + recorder.entries.len() * 16);
+    w.put_bytes(&RECORDING_MAGIC);
+    w.put_u16(RECORDING_VERSION);
+    w.put_u16(ELEMENT_ID_BYTES);
+
+    w.put_u32(recorder.start_snapshot.len() as u32);
+    w.put_bytes(&recorder.start_snapshot);
+
+    w.put_u32(recorder.entries.len() as u32);
+    for (frame, cmd) in recorder.entries.iter() {
+        w.put_u64(*frame);
+        cmd.encode(&mut w);
+    }
+
+    w.put_u64(world.frame);
+    w.put_u64(state_hash(world));
+    Some(w.into_inner())
+}
+
+pub(super) fn replay_recording(world: &mut WorldCore, bytes: &[u8]) -> Result<ReplayReport, String> {
+    if world.recorder.is_some() {
+        return Err("cannot replay while recording".to_string());
+    }
+
+    let mut r = ByteReader::new(bytes);
+    if r.take(4)? != RECORDING_MAGIC {
+        return Err("not an input recording (bad magic)".to_string());
+    }
+    let version = r.u16()?;
+    if version == 0 || version > RECORDING_VERSION {
+        return Err(format!(
+            "unsupported recording version {} (engine supports up to {})",
+            version, RECORDING_VERSION
+        ));
+    }
+    let element_bytes = r.u16()?;
+    r.set_element_bytes(if version >= 2 { element_bytes } else { 1 })?;
+
+    let snapshot_len = r.count(1)?;
+    let start_snapshot = r.take(snapshot_len)?;
+    let count = r.count(9)?;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        let frame = r.u64()?;
+        entries.push((frame, RecordedCommand::decode(&mut r)?));
+    }
+    let final_frame = r.u64()?;
+    let expected_hash = r.u64()?;
+    if r.remaining() != 0 {
+        return Err(format!("recording has {} trailing bytes", r.remaining()));
+    }
+
+    world.load_snapshot(start_snapshot)?;
+    for (i, (frame, cmd)) in entries.into_iter().enumerate() {
+        if world.frame != frame {
+            return Err(format!(
+                "replay diverged at command {}: world is on frame {}, recorded frame {}",
+                i, world.frame, frame
+            ));
+        }
+        apply(world, cmd);
+    }
+
+    let hash = state_hash(world);
+    if world.frame != final_frame || hash != expected_hash {
+        return Err(format!(
+            "replay diverged: final frame {} hash {:016x}, recorded frame {} hash {:016x}",
+            world.frame, hash, final_frame, expected_hash
+        ));
+    }
+
+    Ok(ReplayReport {
+        commands: count as u32,
+        final_frame,
+        state_hash: hash,
+    })
+}
+
+fn apply(world: &mut WorldCore, cmd: RecordedCommand) {
+    match cmd {
+        RecordedCommand::Step { count } => {
+            for _ in 0..count {
+                world.step();
+            }
+        }
+        RecordedCommand::AddParticle { x, y, element } => {
+            world.add_particle(x, y, element);
+        }
+        RecordedCommand::AddParticlesInRadius { cx, cy, radius, element } => {
+            world.add_particles_in_radius(cx, cy, radius, element)
+        }
+        RecordedCommand::RemoveParticle { x, y } => {
+            world.remove_particle(x, y);
+        }
+        RecordedCommand::RemoveParticlesInRadius { cx, cy, radius } => {
+            world.remove_particles_in_radius(cx, cy, radius)
+        }
+        RecordedCommand::Clear => world.clear(),
+        RecordedCommand::SetGravity { x, y } => world.set_gravity(x, y),
+        RecordedCommand::SetAmbientTemperature { temp } => world.set_ambient_temperature(temp),
+        RecordedCommand::SpawnRigidBody { x, y, w, h, element } => {
+            world.spawn_rigid_body(x, y, w, h, element);
+        }
+        RecordedCommand::SpawnRigidCircle { x, y, radius, element } => {
+            world.spawn_rigid_circle(x, y, radius, element);
+        }
+        RecordedCommand::RemoveRigidBody { id } => world.remove_rigid_body(id),
+        RecordedCommand::BeginStroke => world.begin_stroke(),
+        RecordedCommand::EndStroke => world.end_stroke(),
+        RecordedCommand::Undo => {
+            world.undo();
+        }
+        RecordedCommand::Redo => {
+            world.redo();
+        }
+        RecordedCommand::SetHistoryBudget { bytes } => world.set_history_budget(bytes as usize),
+        RecordedCommand::ClearHistory => world.clear_history(),
+        // Failed loads were recorded too; they must fail the same way here.
+        RecordedCommand::LoadSnapshot { bytes } => {
+            let _ = world.load_snapshot(&bytes);
+        }
+        RecordedCommand::LoadContentBundle { json } => {
+            let _ = world.load_content_bundle_json(&json);
+        }
+        RecordedCommand::ReloadContentBundle { json, fallback_key } => {
+            let _ = world.reload_content_bundle_json(&json, fallback_key.as_deref());
+        }
+        RecordedCommand::SetElementProperty { element, field, value } => {
+            let _ = world.set_element_property(element, &field, value);
+        }
+        RecordedCommand::ResetElementProperties => world.reset_element_properties(),
+        RecordedCommand::SetSeed { seed } => world.set_seed(seed),
+        RecordedCommand::CutRegion { x, y, w, h } => {
+            let _ = world.cut_region(x, y, w, h);
+        }
+        RecordedCommand::PasteStamp { bytes, x, y, quarter_turns, mirror, mode } => {
+            if let (Ok(stamp), Some(mode)) = (world.decode_stamp(&bytes), StampBlend::from_code(mode)) {
+                world.paste_stamp(&stamp, x, y, quarter_turns, mirror, mode);
+            }
+        }
+        RecordedCommand::ImportImage { rgba, width, height, x, y, palette, alpha_threshold } => {
+            let mut options = ImageImportOptions::new();
+            options.set_palette(palette);
+            options.set_alpha_threshold(alpha_threshold);
+            let _ = world.import_image(&rgba, width, height, x, y, &options);
+        }
+        RecordedCommand::Paint { shape, element, mode, target } => {
+            if let Some(mode) = BrushMode::from_code(mode, target) {
+                let _ = world.paint(&shape, &Brush::with_mode(element, mode));
+            }
+        }
+        RecordedCommand::FloodFill { x, y, element, connectivity, max_cells } => {
+            let _ = world.flood_fill(x, y, element, connectivity, max_cells);
+        }
+        RecordedCommand::ApplyHeatInRadius { cx, cy, radius, delta, falloff } => {
+            world.apply_heat_in_radius(cx, cy, radius, delta, falloff)
+        }
+        RecordedCommand::SetTemperatureInRadius { cx, cy, radius, temp } => {
+            world.set_temperature_in_radius(cx, cy, radius, temp)
+        }
+        RecordedCommand::ApplyImpulse { cx, cy, radius, vx, vy } => {
+            world.apply_impulse_in_radius(cx, cy, radius, vx, vy);
+        }
+        RecordedCommand::ApplyBlast { cx, cy, radius, strength } => {
+            world.apply_blast(cx, cy, radius, strength);
+        }
+        RecordedCommand::ApplyVortex { cx, cy, radius, strength } => {
+            world.apply_vortex(cx, cy, radius, strength);
+        }
+        RecordedCommand::LoadScene { json } => {
+            let _ = world.load_scene(&json);
+        }
+        RecordedCommand::GenerateTerrain { config } => {
+            let _ = world.generate_terrain(&config);
+        }
+        RecordedCommand::Resize { width, height, anchor } => {
+            if let Some(anchor) = ResizeAnchor::from_code(anchor) {
+                let _ = world.resize(width, height, anchor);
+            }
+        }
+    }
+}
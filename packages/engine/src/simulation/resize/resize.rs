@@ -0,0 +1,116 @@
+use crate::chunks::ChunkGrid;
+use crate::elements::EL_EMPTY;
+use crate::grid::Grid;
+
+use super::WorldCore;
+
+/// Largest grid a resize, scene or snapshot may create (4096 x 4096). Each cell
+/// costs ~20 bytes across the SoA arrays; a failed allocation aborts the wasm instance.
+pub(crate) const MAX_WORLD_CELLS: u64 = 4096 * 4096;
+
+/// Reject empty grids and grids over `MAX_WORLD_CELLS`
+pub(super) fn check_world_size(width: u32, height: u32) -> Result<(), String> {
+    if width == 0 || height == 0 {
+        return Err(format!("invalid world size {}x{}", width, height));
+    }
+    if width as u64 * height as u64 > MAX_WORLD_CELLS {
+        return Err(format!(
+            "world size {}x{} is too large (max {} cells)",
+            width, height, MAX_WORLD_CELLS
+        ));
+    }
+    Ok(())
+}
+
+/// Which part of the old scene stays fixed when the world is resized
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResizeAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ResizeAnchor {
+    /// JS code: 0..=8, row-major from top-left (4 = center)
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::TopLeft,
+            1 => Self::Top,
+            2 => Self::TopRight,
+            3 => Self::Left,
+            4 => Self::Center,
+            5 => Self::Right,
+            6 => Self::BottomLeft,
+            7 => Self::Bottom,
+            8 => Self::BottomRight,
+            _ => return None,
+        })
+    }
+
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// (column, row) in 0..=2
+    fn cell(self) -> (i64, i64) {
+        let code = self.code() as i64;
+        (code % 3, code / 3)
+    }
+}
+
+/// Reallocate the grid to `new_w` x `new_h`, keeping the overlap with the old scene.
+///
+/// The old content is placed according to `anchor`; cells pushed outside are dropped,
+/// new cells are empty air at ambient temperature. Rigid bodies are shifted with the
+/// scene and clipped to the new bounds (bodies with nothing left inside are removed).
+pub(super) fn resize(world: &mut WorldCore, new_w: u32, new_h: u32, anchor: ResizeAnchor) -> Result<(), String> {
+    check_world_size(new_w, new_h)?;
+
+    let old = &world.grid;
+    let (old_w, old_h) = (old.width(), old.height());
+
+    // Offset of the old origin inside the new grid (negative when shrinking).
+    let (col, row) = anchor.cell();
+    let off_x = ((new_w as i64 - old_w as i64) * col / 2) as i32;
+    let off_y = ((new_h as i64 - old_h as i64) * row / 2) as i32;
+
+    let mut grid = Grid::new(new_w, new_h);
+    grid.temperature.fill(world.ambient_temperature);
+
+    // Overlap in old coordinates
+    let x0 = (-off_x).max(0) as u32;
+    let y0 = (-off_y).max(0) as u32;
+    let x1 = (new_w as i64 - off_x as i64).clamp(0, old_w as i64) as u32;
+    let y1 = (new_h as i64 - off_y as i64).clamp(0, old_h as i64) as u32;
+
+    let mut particles = 0u32;
+    if x0 < x1 {
+        let len = (x1 - x0) as usize;
+        for y in y0..y1 {
+            let src = old.index(x0, y);
+            let dst = grid.index((x0 as i32 + off_x) as u32, (y as i32 + off_y) as u32);
+            grid.types[dst..dst + len].copy_from_slice(&old.types[src..src + len]);
+            grid.colors[dst..dst + len].copy_from_slice(&old.colors[src..src + len]);
+            grid.life[dst..dst + len].copy_from_slice(&old.life[src..src + len]);
+            grid.temperature[dst..dst + len].copy_from_slice(&old.temperature[src..src + len]);
+            grid.vx[dst..dst + len].copy_from_slice(&old.vx[src..src + len]);
+            grid.vy[dst..dst + len].copy_from_slice(&old.vy[src..src + len]);
+            particles += old.types[src..src + len].iter().filter(|&&t| t != EL_EMPTY).count() as u32;
+        }
+    }
+
+    world.rigid_bodies.translate(off_x, off_y, new_w, new_h);
+
+    world.grid = grid;
+    world.chunks = ChunkGrid::new(new_w, new_h);
+    world.particle_count = particles;
+    // Recorded cell indices refer to the old layout.
+    world.history.clear();
+    Ok(())
+}
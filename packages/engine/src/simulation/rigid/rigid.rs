@@ -1,8 +1,9 @@
+use crate::elements::ElementId;
 use crate::rigid_body::RigidBody;
 
 use super::WorldCore;
 
-pub(super) fn spawn_rigid_body(world: &mut WorldCore, x: f32, y: f32, w: i32, h: i32, element_id: u8) -> u32 {
+pub(super) fn spawn_rigid_body(world: &mut WorldCore, x: f32, y: f32, w: i32, h: i32, element_id: ElementId) -> u32 {
     let body = RigidBody::new_rect(x, y, w, h, element_id, 0);
     match world
         .rigid_bodies
@@ -16,7 +17,7 @@ pub(super) fn spawn_rigid_body(world: &mut WorldCore, x: f32, y: f32, w: i32, h:
     }
 }
 
-pub(super) fn spawn_rigid_circle(world: &mut WorldCore, x: f32, y: f32, radius: i32, element_id: u8) -> u32 {
+pub(super) fn spawn_rigid_circle(world: &mut WorldCore, x: f32, y: f32, radius: i32, element_id: ElementId) -> u32 {
     let body = RigidBody::new_circle(x, y, radius, element_id, 0);
     match world
         .rigid_bodies
@@ -0,0 +1,256 @@
+//! Declarative JSON scenes: world setup as data instead of spawn code.
+//!
+//! ```json
+//! {
+//!   "formatVersion": 1,
+//!   "width": 256, "height": 192,
+//!   "seed": 1337,
+//!   "gravity": { "x": 0, "y": 1 },
+//!   "ambientTemperature": 20,
+//!   "shapes": [
+//!     { "type": "rect", "element": "base:stone", "x": 0, "y": 180, "w": 256, "h": 12 },
+//!     { "type": "circle", "element": "base:water", "cx": 60, "cy": 100, "radius": 12, "mode": "fillEmpty" },
+//!     { "type": "line", "element": "base:wood", "x0": 10, "y0": 50, "x1": 90, "y1": 70, "thickness": 3 },
+//!     { "type": "ring", "element": "base:metal", "cx": 128, "cy": 96, "radius": 20, "thickness": 2 },
+//!     { "type": "polygon", "element": "base:sand", "points": [[150, 180], [200, 120], [250, 180]] }
+//!   ],
+//!   "rigidBodies": [
+//!     { "type": "box", "element": "base:stone", "x": 40, "y": 20, "w": 8, "h": 8 },
+//!     { "type": "circle", "element": "base:metal", "x": 80, "y": 20, "radius": 5 }
+//!   ]
+//! }
+//! ```
+//!
+//! Shapes are painted in order (later shapes overwrite earlier ones unless
+//! `"mode": "fillEmpty"`); an optional `"temperature"` sets every cell of the
+//! shape afterwards. Element keys resolve through the active content; the
+//! key `base:empty` erases. `seed`, `gravity` and `ambientTemperature` are
+//! optional and keep the current value when omitted.
+
+use serde::Deserialize;
+
+use crate::domain::content::ContentRegistry;
+use crate::elements::ElementId;
+
+use super::brush::{Brush, BrushMode, BrushShape};
+use super::WorldCore;
+
+pub(crate) const SCENE_FORMAT_VERSION: u32 = 1;
+
+/// Result of a successful `load_scene`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SceneLoadReport {
+    /// Particles in the world after loading (including rigid body pixels)
+    pub particles: u32,
+    /// One id per scene rigid body, in order; 0 if it could not be placed
+    pub rigid_bodies: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SceneFile {
+    format_version: u32,
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    seed: Option<u64>,
+    #[serde(default)]
+    gravity: Option<SceneGravity>,
+    #[serde(default)]
+    ambient_temperature: Option<f32>,
+    #[serde(default)]
+    shapes: Vec<SceneShape>,
+    #[serde(default)]
+    rigid_bodies: Vec<SceneBody>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SceneGravity {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum SceneMode {
+    Replace,
+    FillEmpty,
+}
+
+// `deny_unknown_fields` does not combine with `flatten`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SceneShape {
+    element: String,
+    #[serde(default)]
+    mode: Option<SceneMode>,
+    #[serde(default)]
+    temperature: Option<f32>,
+    #[serde(flatten)]
+    geometry: SceneGeometry,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum SceneGeometry {
+    Rect { x: i32, y: i32, w: u32, h: u32 },
+    Circle { cx: i32, cy: i32, radius: i32 },
+    Line {
+        x0: i32,
+        y0: i32,
+        x1: i32,
+        y1: i32,
+        #[serde(default = "one")]
+        thickness: u32,
+    },
+    Ring { cx: i32, cy: i32, radius: i32, thickness: u32 },
+    Polygon { points: Vec<(i32, i32)> },
+}
+
+fn one() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase", deny_unknown_fields)]
+enum SceneBody {
+    Box { element: String, x: f32, y: f32, w: i32, h: i32 },
+    Circle { element: String, x: f32, y: f32, radius: i32 },
+}
+
+impl SceneGeometry {
+    fn to_shape(&self) -> BrushShape {
+        match *self {
+            // Polygon edges on cell borders cover exactly the w x h cells
+            Self::Rect { x, y, w, h } => {
+                let (x1, y1) = (x.saturating_add(w as i32), y.saturating_add(h as i32));
+                BrushShape::Polygon { points: vec![(x, y), (x1, y), (x1, y1), (x, y1)] }
+            }
+            // A ring as thick as its radius is a filled disc
+            Self::Circle { cx, cy, radius } => BrushShape::Ring {
+                cx,
+                cy,
+                radius,
+                thickness: radius.max(0) as u32 + 1,
+            },
+            Self::Line { x0, y0, x1, y1, thickness } => BrushShape::Line { x0, y0, x1, y1, thickness },
+            Self::Ring { cx, cy, radius, thickness } => BrushShape::Ring { cx, cy, radius, thickness },
+            Self::Polygon { ref points } => BrushShape::Polygon { points: points.clone() },
+        }
+    }
+}
+
+/// A fully validated scene: every key resolved, ready to apply
+struct ResolvedScene {
+    width: u32,
+    height: u32,
+    seed: Option<u64>,
+    gravity: Option<(f32, f32)>,
+    ambient_temperature: Option<f32>,
+    shapes: Vec<(BrushShape, Brush, Option<f32>)>,
+    bodies: Vec<SceneBody>,
+    body_elements: Vec<ElementId>,
+}
+
+fn resolve_key(content: &ContentRegistry, key: &str, what: &str) -> Result<ElementId, String> {
+    content
+        .id_by_key(key)
+        .ok_or_else(|| format!("{}: unknown element key '{}'", what, key))
+}
+
+fn resolve(json: &str, content: &ContentRegistry) -> Result<ResolvedScene, String> {
+    let scene: SceneFile = serde_json::from_str(json).map_err(|e| format!("invalid scene: {}", e))?;
+    if scene.format_version == 0 || scene.format_version > SCENE_FORMAT_VERSION {
+        return Err(format!(
+            "unsupported scene format version {} (engine supports up to {})",
+            scene.format_version, SCENE_FORMAT_VERSION
+        ));
+    }
+    super::resize::check_world_size(scene.width, scene.height)?;
+    if scene.ambient_temperature.is_some_and(|t| !t.is_finite()) {
+        return Err("ambientTemperature must be finite".to_string());
+    }
+
+    let mut shapes = Vec::with_capacity(scene.shapes.len());
+    for (i, s) in scene.shapes.iter().enumerate() {
+        let element = resolve_key(content, &s.element, &format!("shape {}", i))?;
+        let mode = match s.mode {
+            Some(SceneMode::FillEmpty) => BrushMode::FillEmpty,
+            Some(SceneMode::Replace) | None => BrushMode::ReplaceAll,
+        };
+        let shape = s.geometry.to_shape();
+        if let BrushShape::Polygon { points } = &shape {
+            if points.len() < 3 {
+                return Err(format!("shape {}: polygon needs at least 3 vertices", i));
+            }
+        }
+        if s.temperature.is_some_and(|t| !t.is_finite()) {
+            return Err(format!("shape {}: temperature must be finite", i));
+        }
+        shapes.push((shape, Brush::with_mode(element, mode), s.temperature));
+    }
+
+    let mut body_elements = Vec::with_capacity(scene.rigid_bodies.len());
+    for (i, b) in scene.rigid_bodies.iter().enumerate() {
+        let key = match b {
+            SceneBody::Box { element, .. } | SceneBody::Circle { element, .. } => element,
+        };
+        body_elements.push(resolve_key(content, key, &format!("rigid body {}", i))?);
+    }
+
+    Ok(ResolvedScene {
+        width: scene.width,
+        height: scene.height,
+        seed: scene.seed,
+        gravity: scene.gravity.map(|g| (g.x, g.y)),
+        ambient_temperature: scene.ambient_temperature,
+        shapes,
+        bodies: scene.rigid_bodies,
+        body_elements,
+    })
+}
+
+/// Replace the world with the scene. Nothing changes if the scene is invalid.
+pub(super) fn load_scene(world: &mut WorldCore, json: &str) -> Result<SceneLoadReport, String> {
+    let scene = resolve(json, &world.content)?;
+
+    if (scene.width, scene.height) != (world.grid.width(), world.grid.height()) {
+        super::resize::resize(world, scene.width, scene.height, super::ResizeAnchor::TopLeft)?;
+    }
+    super::commands::reset(world);
+    if let Some(seed) = scene.seed {
+        super::settings::set_seed(world, seed);
+    }
+    if let Some((x, y)) = scene.gravity {
+        super::settings::set_gravity(world, x, y);
+    }
+    if let Some(temp) = scene.ambient_temperature {
+        super::settings::set_ambient_temperature(world, temp);
+        world.grid.temperature.fill(temp);
+    }
+
+    for (shape, brush, temperature) in &scene.shapes {
+        super::brush::paint::paint(world, shape, brush)?;
+        if let Some(t) = temperature {
+            for (x, y) in shape.cells(world.grid.width(), world.grid.height())? {
+                world.grid.set_temp(x, y, *t);
+            }
+        }
+    }
+
+    let mut rigid_bodies = Vec::with_capacity(scene.bodies.len());
+    for (body, &element) in scene.bodies.iter().zip(&scene.body_elements) {
+        let id = match *body {
+            SceneBody::Box { x, y, w, h, .. } => super::rigid::spawn_rigid_body(world, x, y, w, h, element),
+            SceneBody::Circle { x, y, radius, .. } => super::rigid::spawn_rigid_circle(world, x, y, radius, element),
+        };
+        rigid_bodies.push(id);
+    }
+
+    world.history.clear();
+    Ok(SceneLoadReport {
+        particles: world.particle_count,
+        rigid_bodies,
+    })
+}
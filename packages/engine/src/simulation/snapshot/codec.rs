@@ -0,0 +1,249 @@
+//! Little-endian byte codec shared by the binary snapshot formats.
+//!
+//! Element ids are written in this build's width (`ELEMENT_ID_BYTES`); formats
+//! that carry ids store that width in their header so readers can convert.
+
+use crate::elements::ElementId;
+
+/// Bytes per element id in this build: 1, or 2 with `wide-element-ids`
+pub(crate) const ELEMENT_ID_BYTES: u16 = std::mem::size_of::<ElementId>() as u16;
+
+/// Append-only little-endian writer
+pub(crate) struct ByteWriter {
+    buf: Vec<u8>,
+}
+
+impl ByteWriter {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self { buf: Vec::with_capacity(capacity) }
+    }
+
+    pub(crate) fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+
+    #[inline]
+    pub(crate) fn put_bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    #[inline]
+    pub(crate) fn put_u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    #[inline]
+    pub(crate) fn put_i8(&mut self, v: i8) {
+        self.buf.push(v as u8);
+    }
+
+    #[inline]
+    pub(crate) fn put_u16(&mut self, v: u16) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    #[inline]
+    pub(crate) fn put_u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    #[inline]
+    pub(crate) fn put_i32(&mut self, v: i32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    #[inline]
+    pub(crate) fn put_u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    #[inline]
+    pub(crate) fn put_f32(&mut self, v: f32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    #[inline]
+    pub(crate) fn put_element(&mut self, id: ElementId) {
+        self.buf.extend_from_slice(&id.to_le_bytes());
+    }
+
+    pub(crate) fn put_str(&mut self, s: &str) {
+        self.put_u32(s.len() as u32);
+        self.put_bytes(s.as_bytes());
+    }
+
+    pub(crate) fn put_element_slice(&mut self, ids: &[ElementId]) {
+        self.buf.reserve(ids.len() * ELEMENT_ID_BYTES as usize);
+        for id in ids {
+            self.put_element(*id);
+        }
+    }
+
+    pub(crate) fn put_u16_slice(&mut self, values: &[u16]) {
+        self.buf.reserve(values.len() * 2);
+        for v in values {
+            self.put_u16(*v);
+        }
+    }
+
+    pub(crate) fn put_u32_slice(&mut self, values: &[u32]) {
+        self.buf.reserve(values.len() * 4);
+        for v in values {
+            self.put_u32(*v);
+        }
+    }
+
+    pub(crate) fn put_f32_slice(&mut self, values: &[f32]) {
+        self.buf.reserve(values.len() * 4);
+        for v in values {
+            self.put_f32(*v);
+        }
+    }
+}
+
+/// Bounds-checked little-endian reader; every read fails with a message instead of panicking
+pub(crate) struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Width of stored element ids (see `set_element_bytes`)
+    element_bytes: u16,
+}
+
+impl<'a> ByteReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            element_bytes: ELEMENT_ID_BYTES,
+        }
+    }
+
+    /// Element ids that follow were written `bytes` wide (1 or 2)
+    pub(crate) fn set_element_bytes(&mut self, bytes: u16) -> Result<(), String> {
+        if bytes != 1 && bytes != 2 {
+            return Err(format!("unsupported element id width {} bytes", bytes));
+        }
+        self.element_bytes = bytes;
+        Ok(())
+    }
+
+    pub(crate) fn element_bytes(&self) -> u16 {
+        self.element_bytes
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if n > self.remaining() {
+            return Err(format!(
+                "snapshot truncated: need {} bytes at offset {}, have {}",
+                n,
+                self.pos,
+                self.remaining()
+            ));
+        }
+        let out = &self.data[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(out)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(crate) fn i8(&mut self) -> Result<i8, String> {
+        Ok(self.take(1)?[0] as i8)
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take_array()?))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take_array()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take_array()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take_array()?))
+    }
+
+    pub(crate) fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take_array()?))
+    }
+
+    /// Element id in the stored width; ids too large for this build are an error
+    pub(crate) fn element(&mut self) -> Result<ElementId, String> {
+        let id = match self.element_bytes {
+            1 => self.u8()? as u16,
+            _ => self.u16()?,
+        };
+        element_from_u16(id)
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+    }
+
+    /// Read a `u32` element count and make sure at least `count * min_item_bytes` remain,
+    /// so corrupted counts fail before allocating.
+    pub(crate) fn count(&mut self, min_item_bytes: usize) -> Result<usize, String> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(min_item_bytes) > self.remaining() {
+            return Err(format!("snapshot truncated: count {} exceeds remaining data", count));
+        }
+        Ok(count)
+    }
+
+    pub(crate) fn u16_into(&mut self, out: &mut [u16]) -> Result<(), String> {
+        let bytes = self.take(out.len() * 2)?;
+        for (dst, src) in out.iter_mut().zip(bytes.chunks_exact(2)) {
+            *dst = u16::from_le_bytes([src[0], src[1]]);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn element_into(&mut self, out: &mut [ElementId]) -> Result<(), String> {
+        let width = self.element_bytes as usize;
+        let bytes = self.take(out.len() * width)?;
+        for (dst, src) in out.iter_mut().zip(bytes.chunks_exact(width)) {
+            let id = if width == 1 { src[0] as u16 } else { u16::from_le_bytes([src[0], src[1]]) };
+            *dst = element_from_u16(id)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn u32_into(&mut self, out: &mut [u32]) -> Result<(), String> {
+        let bytes = self.take(out.len() * 4)?;
+        for (dst, src) in out.iter_mut().zip(bytes.chunks_exact(4)) {
+            *dst = u32::from_le_bytes([src[0], src[1], src[2], src[3]]);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn f32_into(&mut self, out: &mut [f32]) -> Result<(), String> {
+        let bytes = self.take(out.len() * 4)?;
+        for (dst, src) in out.iter_mut().zip(bytes.chunks_exact(4)) {
+            *dst = f32::from_le_bytes([src[0], src[1], src[2], src[3]]);
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::useless_conversion)] // identity conversion in wide builds
+fn element_from_u16(id: u16) -> Result<ElementId, String> {
+    ElementId::try_from(id).map_err(|_| format!("element id {} needs a build with wide-element-ids", id))
+}
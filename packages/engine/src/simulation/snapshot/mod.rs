@@ -1 +1,265 @@
+//! Binary world snapshots.
+//!
+//! Layout (all values little-endian):
+//! - header: magic `PSNP`, format version (u16), then reserved (u16) before v4
+//!   and element id width in bytes (u16: 1, or 2 with `wide-element-ids`) from v4
+//! - world: width, height (u32), frame (u64), RNG, particle_count (u32),
+//!   gravity_x, gravity_y, ambient_temperature (f32)
+//!   - v1/v2 RNG: one xorshift32 state (u32), used as the seed on load
+//!   - v3+ RNG: seed (u64), behaviors, reactions, temperature stream states (u32)
+//! - (v2+) element key table: count, then `(id, key: string)` entries
+//!   (element ids are one byte before v4, id-width bytes from v4)
+//! - grid SoA arrays, one after another: types, colors, life, temperature, vx, vy
+//! - rigid bodies: next_id, body count, then each body with its pixels and
+//!   last rasterized cells
+//!
+//! The `updated` array is not stored: it is reset at the start of every step.
+//! Readers must keep accepting every older format version.
+//!
+//! Element ids are remapped by key on load (see `remap`), so a snapshot stays
+//! valid when the active content bundle assigns different ids.
 
+pub(crate) mod codec;
+pub(crate) mod remap;
+
+use crate::chunks::ChunkGrid;
+use crate::grid::Grid;
+use crate::rigid_body::{BodyPixel, RigidBody, Vec2};
+use crate::rigid_body_system::RigidBodySystem;
+
+use self::codec::{ByteReader, ByteWriter, ELEMENT_ID_BYTES};
+use self::remap::{apply_remap, read_key_table, write_key_table, ElementRemap};
+use super::random::RngStreams;
+use super::WorldCore;
+
+pub(crate) const SNAPSHOT_MAGIC: [u8; 4] = *b"PSNP";
+pub(crate) const SNAPSHOT_VERSION: u16 = 4;
+
+/// Result of a successful `load_snapshot`
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotLoadReport {
+    /// Saved element keys the current content does not have (`#id` for keyless v1 snapshots)
+    pub missing_elements: Vec<String>,
+    /// Cells that were emptied because their element is missing
+    pub cleared_cells: u32,
+}
+
+/// Per-cell bytes of the grid section without types: colors(4)+life(2)+temp(4)+vx(4)+vy(4)
+const GRID_BYTES_PER_CELL: usize = 18;
+
+pub(super) fn save_snapshot(world: &WorldCore) -> Vec<u8> {
+    let size = world.grid.size();
+    let mut w = ByteWriter::with_capacity(64 + size * (GRID_BYTES_PER_CELL + ELEMENT_ID_BYTES as usize));
+
+    w.put_bytes(&SNAPSHOT_MAGIC);
+    w.put_u16(SNAPSHOT_VERSION);
+    w.put_u16(ELEMENT_ID_BYTES);
+
+    w.put_u32(world.grid.width());
+    w.put_u32(world.grid.height());
+    w.put_u64(world.frame);
+    w.put_u64(world.rng.seed);
+    w.put_u32(world.rng.behaviors);
+    w.put_u32(world.rng.reactions);
+    w.put_u32(world.rng.temperature);
+    w.put_u32(world.particle_count);
+    w.put_f32(world.gravity_x);
+    w.put_f32(world.gravity_y);
+    w.put_f32(world.ambient_temperature);
+
+    write_key_table(&mut w, &world.content);
+    write_grid(&mut w, &world.grid);
+    write_rigid_bodies(&mut w, &world.rigid_bodies);
+
+    w.into_inner()
+}
+
+pub(super) fn load_snapshot(world: &mut WorldCore, bytes: &[u8]) -> Result<SnapshotLoadReport, String> {
+    let mut r = ByteReader::new(bytes);
+
+    if r.take(4)? != SNAPSHOT_MAGIC {
+        return Err("not a world snapshot (bad magic)".to_string());
+    }
+    let version = r.u16()?;
+    if version == 0 || version > SNAPSHOT_VERSION {
+        return Err(format!(
+            "unsupported snapshot version {} (engine supports up to {})",
+            version, SNAPSHOT_VERSION
+        ));
+    }
+    let element_bytes = r.u16()?;
+    r.set_element_bytes(if version >= 4 { element_bytes } else { 1 })?;
+
+    let width = r.u32()?;
+    let height = r.u32()?;
+    if width == 0 || height == 0 {
+        return Err(format!("invalid snapshot dimensions {}x{}", width, height));
+    }
+    super::resize::check_world_size(width, height)?;
+
+    let frame = r.u64()?;
+    let rng = if version >= 3 {
+        RngStreams::restore(r.u64()?, r.u32()?, r.u32()?, r.u32()?)
+    } else {
+        RngStreams::from_seed(r.u32()? as u64)
+    };
+    let particle_count = r.u32()?;
+    let gravity_x = r.f32()?;
+    let gravity_y = r.f32()?;
+    let ambient_temperature = r.f32()?;
+
+    let remap = if version >= 2 {
+        let saved_keys = read_key_table(&mut r)?;
+        ElementRemap::from_key_table(&saved_keys, &world.content)
+    } else {
+        ElementRemap::raw_ids(&world.content)
+    };
+
+    let size_ok = (width as usize)
+        .checked_mul(height as usize)
+        .is_some_and(|s| s.saturating_mul(GRID_BYTES_PER_CELL + r.element_bytes() as usize) <= r.remaining());
+    if !size_ok {
+        return Err(format!("snapshot truncated for {}x{} grid", width, height));
+    }
+
+    let mut grid = Grid::new(width, height);
+    read_grid(&mut r, &mut grid)?;
+    let (mut bodies, next_body_id) = read_rigid_bodies(&mut r)?;
+
+    if r.remaining() != 0 {
+        return Err(format!("snapshot has {} trailing bytes", r.remaining()));
+    }
+
+    let outcome = apply_remap(&remap, &mut grid, &mut bodies);
+
+    // Commit only after the whole snapshot decoded successfully.
+    if world.grid.width() != width || world.grid.height() != height {
+        world.chunks = ChunkGrid::new(width, height);
+    }
+    world.grid = grid;
+    world.rigid_bodies = RigidBodySystem::from_parts(bodies, next_body_id);
+    world.frame = frame;
+    world.rng = rng;
+    world.particle_count = particle_count.saturating_sub(outcome.cleared_cells);
+    world.gravity_x = gravity_x;
+    world.gravity_y = gravity_y;
+    world.ambient_temperature = ambient_temperature;
+
+    Ok(SnapshotLoadReport {
+        missing_elements: outcome.missing_elements,
+        cleared_cells: outcome.cleared_cells,
+    })
+}
+
+fn write_grid(w: &mut ByteWriter, grid: &Grid) {
+    w.put_element_slice(&grid.types);
+    w.put_u32_slice(&grid.colors);
+    w.put_u16_slice(&grid.life);
+    w.put_f32_slice(&grid.temperature);
+    w.put_f32_slice(&grid.vx);
+    w.put_f32_slice(&grid.vy);
+}
+
+fn read_grid(r: &mut ByteReader, grid: &mut Grid) -> Result<(), String> {
+    r.element_into(&mut grid.types)?;
+    r.u32_into(&mut grid.colors)?;
+    r.u16_into(&mut grid.life)?;
+    r.f32_into(&mut grid.temperature)?;
+    r.f32_into(&mut grid.vx)?;
+    r.f32_into(&mut grid.vy)?;
+    Ok(())
+}
+
+fn write_rigid_bodies(w: &mut ByteWriter, system: &RigidBodySystem) {
+    w.put_u32(system.next_id());
+    w.put_u32(system.bodies().len() as u32);
+    for body in system.bodies() {
+        w.put_u32(body.id);
+        w.put_u8(body.active as u8);
+        w.put_f32(body.pos.x);
+        w.put_f32(body.pos.y);
+        w.put_f32(body.velocity.x);
+        w.put_f32(body.velocity.y);
+        w.put_f32(body.angle);
+        w.put_f32(body.angular_vel);
+        w.put_f32(body.mass);
+        w.put_f32(body.moment_of_inertia);
+        w.put_f32(body.half_width);
+        w.put_f32(body.half_height);
+        w.put_f32(body.prev_pos.x);
+        w.put_f32(body.prev_pos.y);
+        w.put_f32(body.prev_angle);
+        w.put_f32(body.restitution);
+
+        w.put_u32(body.pixels.len() as u32);
+        for p in body.pixels.iter() {
+            w.put_i8(p.dx);
+            w.put_i8(p.dy);
+            w.put_element(p.element);
+            w.put_u8(p.color_seed);
+        }
+
+        w.put_u32(body.prev_world_coords.len() as u32);
+        for &(x, y) in body.prev_world_coords.iter() {
+            w.put_i32(x);
+            w.put_i32(y);
+        }
+    }
+}
+
+fn read_rigid_bodies(r: &mut ByteReader) -> Result<(Vec<RigidBody>, u32), String> {
+    let next_id = r.u32()?;
+    let count = r.count(4)?;
+    let mut bodies = Vec::with_capacity(count);
+    for _ in 0..count {
+        let id = r.u32()?;
+        let active = r.u8()? != 0;
+        let pos = Vec2::new(r.f32()?, r.f32()?);
+        let velocity = Vec2::new(r.f32()?, r.f32()?);
+        let angle = r.f32()?;
+        let angular_vel = r.f32()?;
+        let mass = r.f32()?;
+        let moment_of_inertia = r.f32()?;
+        let half_width = r.f32()?;
+        let half_height = r.f32()?;
+        let prev_pos = Vec2::new(r.f32()?, r.f32()?);
+        let prev_angle = r.f32()?;
+        let restitution = r.f32()?;
+
+        let pixel_count = r.count(4)?;
+        let mut pixels = Vec::with_capacity(pixel_count);
+        for _ in 0..pixel_count {
+            pixels.push(BodyPixel {
+                dx: r.i8()?,
+                dy: r.i8()?,
+                element: r.element()?,
+                color_seed: r.u8()?,
+            });
+        }
+
+        let coord_count = r.count(8)?;
+        let mut prev_world_coords = Vec::with_capacity(coord_count);
+        for _ in 0..coord_count {
+            prev_world_coords.push((r.i32()?, r.i32()?));
+        }
+
+        bodies.push(RigidBody {
+            pos,
+            velocity,
+            angle,
+            angular_vel,
+            mass,
+            moment_of_inertia,
+            active,
+            id,
+            pixels,
+            half_width,
+            half_height,
+            prev_pos,
+            prev_angle,
+            prev_world_coords,
+            restitution,
+        });
+    }
+    Ok((bodies, next_id))
+}
@@ -0,0 +1,147 @@
+//! Element id remapping for snapshots saved under a different content bundle.
+//!
+//! Ids are only stable within one `bundle.json`; keys (`pack:name`) are stable
+//! across bundles. Snapshots carry their key table so ids can be translated
+//! against the registry that is active at load time.
+
+use std::collections::BTreeSet;
+
+use crate::domain::content::ContentRegistry;
+use crate::elements::{ElementId, EL_EMPTY};
+use crate::grid::Grid;
+use crate::rigid_body::RigidBody;
+
+use super::codec::{ByteReader, ByteWriter};
+
+/// Saved id -> current id; `None` when the saved element does not exist anymore
+/// (tables only cover the saved ids, not the whole id space)
+pub(crate) struct ElementRemap {
+    table: Vec<Option<ElementId>>,
+    saved_keys: Vec<Option<String>>,
+}
+
+impl ElementRemap {
+    /// Resolve a saved key table against the current registry
+    pub(crate) fn from_key_table<K: AsRef<str>>(saved: &[(K, ElementId)], content: &ContentRegistry) -> Self {
+        let len = saved.iter().map(|(_, id)| *id as usize + 1).max().unwrap_or(0).max(EL_EMPTY as usize + 1);
+        let mut table: Vec<Option<ElementId>> = vec![None; len];
+        let mut saved_keys: Vec<Option<String>> = vec![None; len];
+
+        for (key, saved_id) in saved.iter() {
+            let idx = *saved_id as usize;
+            let key = key.as_ref();
+            if saved_keys[idx].is_none() {
+                saved_keys[idx] = Some(key.to_string());
+            }
+            // A saved id may have several aliases; the first one the registry knows wins.
+            if table[idx].is_none() {
+                table[idx] = content.id_by_key(key);
+            }
+        }
+        table[EL_EMPTY as usize] = Some(EL_EMPTY);
+
+        Self { table, saved_keys }
+    }
+
+    /// Snapshots without a key table: ids are taken as-is when the registry has them
+    pub(super) fn raw_ids(content: &ContentRegistry) -> Self {
+        let len = content.element_count();
+        let table = (0..len).map(|idx| Some(idx as ElementId)).collect();
+        Self {
+            table,
+            saved_keys: vec![None; len],
+        }
+    }
+
+    /// Send every unresolved id (except empty) to `fallback`
+    pub(crate) fn with_fallback(mut self, fallback: ElementId) -> Self {
+        for (idx, mapped) in self.table.iter_mut().enumerate() {
+            if mapped.is_none() && idx != EL_EMPTY as usize {
+                *mapped = Some(fallback);
+            }
+        }
+        self
+    }
+
+    #[inline]
+    pub(crate) fn map(&self, saved: ElementId) -> Option<ElementId> {
+        self.table.get(saved as usize).copied().flatten()
+    }
+
+    fn describe(&self, saved: ElementId) -> String {
+        self.saved_keys
+            .get(saved as usize)
+            .cloned()
+            .flatten()
+            .unwrap_or_else(|| format!("#{}", saved))
+    }
+}
+
+/// Element key table: count, then `(id, key: string)` entries
+pub(crate) fn write_key_table(w: &mut ByteWriter, content: &ContentRegistry) {
+    let table = content.element_key_table();
+    w.put_u32(table.len() as u32);
+    for (key, id) in table {
+        w.put_element(id);
+        w.put_str(key);
+    }
+}
+
+pub(crate) fn read_key_table(r: &mut ByteReader) -> Result<Vec<(String, ElementId)>, String> {
+    let count = r.count(5)?;
+    let mut table = Vec::with_capacity(count);
+    for _ in 0..count {
+        let id = r.element()?;
+        let key = r.string()?;
+        table.push((key, id));
+    }
+    Ok(table)
+}
+
+/// Outcome of remapping one snapshot
+pub(super) struct RemapOutcome {
+    pub(super) missing_elements: Vec<String>,
+    pub(super) cleared_cells: u32,
+}
+
+/// Translate every cell and body pixel; cells with missing elements become empty,
+/// body pixels with missing elements are dropped (and empty bodies with them).
+pub(super) fn apply_remap(remap: &ElementRemap, grid: &mut Grid, bodies: &mut Vec<RigidBody>) -> RemapOutcome {
+    let mut missing: BTreeSet<ElementId> = BTreeSet::new();
+    let mut cleared_cells = 0u32;
+
+    for idx in 0..grid.size() {
+        let saved = grid.types[idx];
+        if saved == EL_EMPTY {
+            continue;
+        }
+        match remap.map(saved) {
+            Some(id) => grid.types[idx] = id,
+            None => {
+                let (x, y) = grid.coords(idx);
+                grid.clear_cell(x, y);
+                missing.insert(saved);
+                cleared_cells += 1;
+            }
+        }
+    }
+
+    for body in bodies.iter_mut() {
+        body.pixels.retain_mut(|p| match remap.map(p.element) {
+            Some(id) => {
+                p.element = id;
+                true
+            }
+            None => {
+                missing.insert(p.element);
+                false
+            }
+        });
+    }
+    bodies.retain(|b| !b.pixels.is_empty());
+
+    RemapOutcome {
+        missing_elements: missing.into_iter().map(|id| remap.describe(id)).collect(),
+        cleared_cells,
+    }
+}
@@ -0,0 +1,201 @@
+//! Stamps: rectangular grid regions that can be copied, transformed and pasted.
+//!
+//! A stamp keeps per-cell type, life, temperature and velocity. Colors are not
+//! stored: pasted particles are recolored from the active palette, like brushes.
+//!
+//! Serialized layout (little-endian):
+//! - header: magic `PSTM`, format version (u16), then reserved (u16) in v1 and
+//!   element id width in bytes (u16) from v2
+//! - width, height (u32)
+//! - element key table (same as snapshots), so ids survive content changes
+//! - SoA arrays: types, life, temperature, vx, vy
+//!
+//! v1 stamps always use one-byte element ids.
+
+pub(crate) mod place;
+
+use crate::domain::content::ContentRegistry;
+use crate::elements::{ElementId, EL_EMPTY};
+
+use super::snapshot::codec::{ByteReader, ByteWriter, ELEMENT_ID_BYTES};
+use super::snapshot::remap::{read_key_table, write_key_table, ElementRemap};
+
+pub(crate) const STAMP_MAGIC: [u8; 4] = *b"PSTM";
+pub(crate) const STAMP_VERSION: u16 = 2;
+
+/// life(2)+temp(4)+vx(4)+vy(4), plus the element id width
+const STAMP_BYTES_PER_CELL: usize = 14;
+
+/// How pasted cells combine with what is already in the world
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StampBlend {
+    /// Every stamp cell replaces the target cell (empty cells clear it)
+    Overwrite,
+    /// Non-empty stamp cells are placed only where the world is empty
+    IntoEmpty,
+    /// Non-empty stamp cells replace the target; empty stamp cells leave it alone
+    SkipEmptySource,
+}
+
+impl StampBlend {
+    /// JS code: 0 = overwrite, 1 = into-empty, 2 = skip-empty-source
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Overwrite),
+            1 => Some(Self::IntoEmpty),
+            2 => Some(Self::SkipEmptySource),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+/// A captured rectangular region of the grid
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stamp {
+    width: u32,
+    height: u32,
+    types: Vec<ElementId>,
+    life: Vec<u16>,
+    temperature: Vec<f32>,
+    vx: Vec<f32>,
+    vy: Vec<f32>,
+}
+
+impl Stamp {
+    fn empty(width: u32, height: u32, ambient_temp: f32) -> Self {
+        let size = (width as usize) * (height as usize);
+        Self {
+            width,
+            height,
+            types: vec![EL_EMPTY; size],
+            life: vec![0; size],
+            temperature: vec![ambient_temp; size],
+            vx: vec![0.0; size],
+            vy: vec![0.0; size],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn element_at(&self, x: u32, y: u32) -> Option<ElementId> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.types[(y * self.width + x) as usize])
+    }
+
+    /// Non-empty cells
+    pub fn particle_count(&self) -> u32 {
+        self.types.iter().filter(|&&t| t != EL_EMPTY).count() as u32
+    }
+
+    /// Mirror horizontally (optional), then rotate clockwise by `quarter_turns` * 90°.
+    /// Velocities are transformed along with positions.
+    pub fn transformed(&self, quarter_turns: u8, mirror: bool) -> Stamp {
+        let turns = quarter_turns & 3;
+        let (w, h) = (self.width, self.height);
+        let (out_w, out_h) = if turns % 2 == 1 { (h, w) } else { (w, h) };
+        let mut out = Stamp::empty(out_w, out_h, 0.0);
+
+        for sy in 0..h {
+            for sx in 0..w {
+                let src = (sy * w + sx) as usize;
+                let (x, y) = if mirror { (w - 1 - sx, sy) } else { (sx, sy) };
+                let (vx, vy) = if mirror {
+                    (-self.vx[src], self.vy[src])
+                } else {
+                    (self.vx[src], self.vy[src])
+                };
+                // Clockwise in screen space (y down)
+                let ((x, y), (vx, vy)) = match turns {
+                    1 => ((h - 1 - y, x), (-vy, vx)),
+                    2 => ((w - 1 - x, h - 1 - y), (-vx, -vy)),
+                    3 => ((y, w - 1 - x), (vy, -vx)),
+                    _ => ((x, y), (vx, vy)),
+                };
+                let dst = (y * out_w + x) as usize;
+                out.types[dst] = self.types[src];
+                out.life[dst] = self.life[src];
+                out.temperature[dst] = self.temperature[src];
+                out.vx[dst] = vx;
+                out.vy[dst] = vy;
+            }
+        }
+        out
+    }
+
+    /// Encode with the content's key table so the stamp can be pasted under other bundles
+    pub fn to_bytes(&self, content: &ContentRegistry) -> Vec<u8> {
+        let size = self.types.len();
+        let mut w = ByteWriter::with_capacity(64 + size * (STAMP_BYTES_PER_CELL + ELEMENT_ID_BYTES as usize));
+        w.put_bytes(&STAMP_MAGIC);
+        w.put_u16(STAMP_VERSION);
+        w.put_u16(ELEMENT_ID_BYTES);
+        w.put_u32(self.width);
+        w.put_u32(self.height);
+        write_key_table(&mut w, content);
+        w.put_element_slice(&self.types);
+        w.put_u16_slice(&self.life);
+        w.put_f32_slice(&self.temperature);
+        w.put_f32_slice(&self.vx);
+        w.put_f32_slice(&self.vy);
+        w.into_inner()
+    }
+
+    /// Decode a stamp, remapping element ids by key against `content`.
+    /// Cells whose element does not exist in `content` become empty.
+    pub fn from_bytes(bytes: &[u8], content: &ContentRegistry) -> Result<Stamp, String> {
+        let mut r = ByteReader::new(bytes);
+        if r.take(4)? != STAMP_MAGIC {
+            return Err("not a stamp (bad magic)".to_string());
+        }
+        let version = r.u16()?;
+        if version == 0 || version > STAMP_VERSION {
+            return Err(format!(
+                "unsupported stamp version {} (engine supports up to {})",
+                version, STAMP_VERSION
+            ));
+        }
+        let element_bytes = r.u16()?;
+        r.set_element_bytes(if version >= 2 { element_bytes } else { 1 })?;
+
+        let width = r.u32()?;
+        let height = r.u32()?;
+        if width == 0 || height == 0 {
+            return Err(format!("invalid stamp dimensions {}x{}", width, height));
+        }
+        let remap = ElementRemap::from_key_table(&read_key_table(&mut r)?, content);
+
+        let size_ok = (width as usize)
+            .checked_mul(height as usize)
+            .is_some_and(|s| s.saturating_mul(STAMP_BYTES_PER_CELL + r.element_bytes() as usize) <= r.remaining());
+        if !size_ok {
+            return Err(format!("stamp truncated for {}x{} region", width, height));
+        }
+
+        let mut stamp = Stamp::empty(width, height, 0.0);
+        r.element_into(&mut stamp.types)?;
+        r.u16_into(&mut stamp.life)?;
+        r.f32_into(&mut stamp.temperature)?;
+        r.f32_into(&mut stamp.vx)?;
+        r.f32_into(&mut stamp.vy)?;
+        if r.remaining() != 0 {
+            return Err(format!("stamp has {} trailing bytes", r.remaining()));
+        }
+
+        for t in stamp.types.iter_mut() {
+            *t = remap.map(*t).unwrap_or(EL_EMPTY);
+        }
+        Ok(stamp)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::elements::EL_EMPTY;
+
+use super::super::WorldCore;
+use super::{Stamp, StampBlend};
+
+/// World cell at offset `(sx, sy)` from `(x, y)`, if inside the world
+/// (64-bit math: JS may pass corners near `i32::MAX`)
+fn world_cell(world: &WorldCore, x: i32, y: i32, sx: u32, sy: u32) -> Option<(u32, u32)> {
+    let gx = x as i64 + sx as i64;
+    let gy = y as i64 + sy as i64;
+    let inside = (0..world.grid.width() as i64).contains(&gx) && (0..world.grid.height() as i64).contains(&gy);
+    inside.then_some((gx as u32, gy as u32))
+}
+
+/// Capture `w` x `h` cells starting at `(x, y)`; cells outside the world are empty.
+/// The region may not be larger than the world, which bounds the allocation.
+pub(crate) fn copy_region(world: &WorldCore, x: i32, y: i32, w: u32, h: u32) -> Result<Stamp, String> {
+    if w == 0 || h == 0 {
+        return Err(format!("invalid region size {}x{}", w, h));
+    }
+    if w > world.grid.width() || h > world.grid.height() {
+        return Err(format!(
+            "region {}x{} is larger than the world ({}x{})",
+            w,
+            h,
+            world.grid.width(),
+            world.grid.height()
+        ));
+    }
+    let mut stamp = Stamp::empty(w, h, world.ambient_temperature);
+    let grid = &world.grid;
+    for sy in 0..h {
+        for sx in 0..w {
+            let Some((gx, gy)) = world_cell(world, x, y, sx, sy) else {
+                continue;
+            };
+            let src = grid.index(gx, gy);
+            let dst = (sy * w + sx) as usize;
+            stamp.types[dst] = grid.types[src];
+            stamp.life[dst] = grid.life[src];
+            stamp.temperature[dst] = grid.temperature[src];
+            stamp.vx[dst] = grid.vx[src];
+            stamp.vy[dst] = grid.vy[src];
+        }
+    }
+    Ok(stamp)
+}
+
+/// Copy the region, then remove its particles (user edit: recorded in undo history)
+pub(crate) fn cut_region(world: &mut WorldCore, x: i32, y: i32, w: u32, h: u32) -> Result<Stamp, String> {
+    let stamp = copy_region(world, x, y, w, h)?;
+    for sy in 0..h {
+        for sx in 0..w {
+            if let Some((gx, gy)) = world_cell(world, x, y, sx, sy) {
+                super::super::commands::remove_particle(world, gx, gy);
+            }
+        }
+    }
+    Ok(stamp)
+}
+
+/// Paste `stamp` with its (transformed) top-left corner at `(x, y)`.
+/// Returns the number of particles placed.
+pub(crate) fn paste_stamp(
+    world: &mut WorldCore,
+    stamp: &Stamp,
+    x: i32,
+    y: i32,
+    quarter_turns: u8,
+    mirror: bool,
+    mode: StampBlend,
+) -> u32 {
+    let transformed;
+    let stamp = if quarter_turns & 3 == 0 && !mirror {
+        stamp
+    } else {
+        transformed = stamp.transformed(quarter_turns, mirror);
+        &transformed
+    };
+
+    let mut placed = 0u32;
+    for sy in 0..stamp.height {
+        for sx in 0..stamp.width {
+            let Some((gx, gy)) = world_cell(world, x, y, sx, sy) else {
+                continue;
+            };
+            let src = (sy * stamp.width + sx) as usize;
+            let element = stamp.types[src];
+            let target_empty = world.grid.types[world.grid.index(gx, gy)] == EL_EMPTY;
+
+            let write = match mode {
+                StampBlend::Overwrite => true,
+                StampBlend::IntoEmpty => element != EL_EMPTY && target_empty,
+                StampBlend::SkipEmptySource => element != EL_EMPTY,
+            };
+            if !write {
+                continue;
+            }
+
+            if element == EL_EMPTY {
+                world.history.record_cell(&world.grid, gx, gy);
+                let idx = world.grid.index(gx, gy);
+                world.grid.clear_cell(gx, gy);
+                world.grid.temperature[idx] = stamp.temperature[src];
+                if !target_empty {
+                    world.particle_count = world.particle_count.saturating_sub(1);
+                }
+                continue;
+            }
+
+            let seed = ((gx * 7 + gy * 13 + world.frame as u32) & 31) as u8;
+            let Some(color) = world.content.color_with_variation(element, seed) else {
+                continue;
+            };
+            world.history.record_cell(&world.grid, gx, gy);
+            let idx = world.grid.index(gx, gy);
+            world
+                .grid
+                .set_particle(gx, gy, element, color, stamp.life[src], stamp.temperature[src]);
+            world.grid.vx[idx] = stamp.vx[src];
+            world.grid.vy[idx] = stamp.vy[src];
+            if target_empty {
+                world.particle_count += 1;
+            }
+            placed += 1;
+        }
+    }
+    placed
+}
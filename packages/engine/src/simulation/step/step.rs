@@ -163,7 +163,7 @@ pub(super) fn step(world: &mut WorldCore) {
             &mut world.grid,
             world.ambient_temperature,
             world.frame,
-            &mut world.rng_state,
+            &mut world.rng.temperature,
             world.perf_split || world.perf_detailed,
         );
         world.perf_stats.temperature_ms = t0.elapsed_ms();
@@ -177,7 +177,7 @@ pub(super) fn step(world: &mut WorldCore) {
             &mut world.grid,
             world.ambient_temperature,
             world.frame,
-            &mut world.rng_state,
+            &mut world.rng.temperature,
             false,
         );
     }
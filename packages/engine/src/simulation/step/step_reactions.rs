@@ -1,6 +1,7 @@
 use crate::elements::{ElementId, EL_EMPTY};
-use crate::reactions::Reaction;
+use crate::reactions::{pick_reaction, Reaction};
 
+use super::heat::ABSOLUTE_ZERO;
 use super::WorldCore;
 
 pub(super) fn process_reactions(world: &mut WorldCore, x: u32, y: u32, element: ElementId) {
@@ -10,7 +11,7 @@ pub(super) fn process_reactions(world: &mut WorldCore, x: u32, y: u32, element:
 
     // Pick a random neighbor
     // PHASE 1 OPT: & 3 instead of % 4 (saves ~40 CPU cycles)
-    let dir = super::xorshift32(&mut world.rng_state) & 3;
+    let dir = super::xorshift32(&mut world.rng.reactions) & 3;
     let xi = x as i32;
     let yi = y as i32;
 
@@ -30,18 +31,22 @@ pub(super) fn process_reactions(world: &mut WorldCore, x: u32, y: u32, element:
         return;
     }
 
-    // Phase 1: O(1) reaction lookup from LUT
-    if let Some(reaction) = world.content.reaction(element, neighbor_type) {
-        // Roll the dice (chance is 0-255 in new system)
-        let roll = (super::xorshift32(&mut world.rng_state) & 0xFF) as u8;
-        if roll >= reaction.chance {
-            return;
-        }
-
-        // Copy reaction to release the borrow before apply
-        let r = *reaction;
-        world.apply_reaction(x, y, nx as u32, ny as u32, &r);
+    // Sparse table lookup; one roll picks among the pair's candidates
+    let candidates = world.content.reactions(element, neighbor_type);
+    if candidates.is_empty() {
+        return;
     }
+    // Temperature gate: the mean of both cells. Gated-out candidates take no
+    // part in the roll, so they do not shrink the odds of the others.
+    let temp = (world.grid.get_temp(xi, yi) + world.grid.get_temp(nx, ny)) * 0.5;
+    // Roll the dice (chance is 0-255 in new system)
+    let roll = (super::xorshift32(&mut world.rng.reactions) & 0xFF) as u8;
+
+    // Copy reaction to release the borrow before apply
+    let Some(&r) = pick_reaction(candidates, roll, temp) else {
+        return;
+    };
+    world.apply_reaction(x, y, nx as u32, ny as u32, &r);
 }
 
 pub(super) fn apply_reaction(
@@ -58,7 +63,7 @@ pub(super) fn apply_reaction(
 
     // A. Transform the TARGET (victim)
     if reaction.target_becomes == EL_EMPTY {
-        world.remove_particle(target_x, target_y);
+        super::commands::despawn_particle(world, target_x, target_y);
     } else {
         world.replace_particle(target_x, target_y, reaction.target_becomes);
     }
@@ -66,13 +71,21 @@ pub(super) fn apply_reaction(
     // B. Transform the SOURCE (aggressor) - BILATERAL!
     if reaction.source_becomes != Reaction::NO_CHANGE {
         if reaction.source_becomes == EL_EMPTY {
-            world.remove_particle(src_x, src_y);
+            super::commands::despawn_particle(world, src_x, src_y);
         } else {
             world.replace_particle(src_x, src_y, reaction.source_becomes);
         }
     }
 
-    // C. Spawn byproduct (smoke, steam)
+    // C. Release (or absorb) heat in both cells
+    if reaction.heat_delta != 0.0 {
+        for (cx, cy) in [(src_x, src_y), (target_x, target_y)] {
+            let t = world.grid.get_temp(cx as i32, cy as i32) + reaction.heat_delta;
+            world.grid.set_temp(cx, cy, t.max(ABSOLUTE_ZERO));
+        }
+    }
+
+    // D. Spawn byproduct (smoke, steam)
     if reaction.spawn != EL_EMPTY {
         let sxi = src_x as i32;
         let syi = src_y as i32;
@@ -81,9 +94,9 @@ pub(super) fn apply_reaction(
 
         // Try to spawn above the reaction site
         if world.grid.is_empty(sxi, syi - 1) {
-            world.add_particle(src_x, (syi - 1) as u32, reaction.spawn);
+            super::commands::spawn_particle(world, src_x, (syi - 1) as u32, reaction.spawn);
         } else if world.grid.is_empty(txi, tyi - 1) {
-            world.add_particle(target_x, (tyi - 1) as u32, reaction.spawn);
+            super::commands::spawn_particle(world, target_x, (tyi - 1) as u32, reaction.spawn);
         }
     }
 }
@@ -92,7 +105,7 @@ pub(super) fn replace_particle(world: &mut WorldCore, x: u32, y: u32, element: E
     let seed = ((x * 7 + y * 13 + world.frame as u32) & 31) as u8;
 
     let Some(props) = world.content.props(element) else {
-        world.remove_particle(x, y);
+        super::commands::despawn_particle(world, x, y);
         return;
     };
 
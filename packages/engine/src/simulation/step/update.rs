@@ -77,7 +77,7 @@ pub(super) fn update_particle_chunked(world: &mut WorldCore, x: u32, y: u32) ->
             gravity_x: world.gravity_x,
             gravity_y: world.gravity_y,
             ambient_temp: world.ambient_temperature,
-            rng: &mut world.rng_state,
+            rng: &mut world.rng.behaviors,
         };
 
         if world.perf_enabled {
@@ -6,6 +6,7 @@ use crate::elements::{
     EL_FIRE,
     EL_ICE,
     EL_LAVA,
+    EL_OIL,
     EL_SAND,
     EL_STEAM,
     EL_STONE,
@@ -14,6 +15,40 @@ use crate::elements::{
 };
 use crate::physics::raycast_move;
 
+/// Minimal content bundle: ids are assigned in slice order, `(key, category)` per element.
+fn test_bundle_json(elements: &[(&str, &str)]) -> String {
+    let mut els = Vec::new();
+    let mut key_to_id = serde_json::Map::new();
+    for (id, (key, category)) in elements.iter().enumerate() {
+        els.push(serde_json::json!({
+            "id": id,
+            "key": key,
+            "category": category,
+            "color": 0xFF102030u32 + id as u32,
+            "density": 1000.0,
+            "dispersion": 0,
+            "lifetime": 0,
+            "defaultTemp": 20.0,
+            "heatConductivity": 10,
+            "bounce": 0.0,
+            "friction": 0.9,
+            "flags": {
+                "flammable": false, "conductive": false, "corrosive": false,
+                "hot": false, "cold": false, "ignoreGravity": false, "rigid": false
+            },
+            "behavior": null
+        }));
+        key_to_id.insert(key.to_string(), serde_json::json!(id));
+    }
+    serde_json::json!({
+        "formatVersion": 1,
+        "elements": els,
+        "elementKeyToId": key_to_id,
+        "reactions": []
+    })
+    .to_string()
+}
+
 #[test]
 fn utility_clone_spawns_and_updates_counts() {
     let mut world = WorldCore::new(64, 64);
@@ -154,10 +189,1104 @@ fn abi_layout_data_lengths_are_consistent() {
     let world = WorldCore::new(10, 11);
     let data = world.abi_layout_data();
 
-    assert_eq!(data.types_len_bytes, data.types_len_elements * std::mem::size_of::<u8>());
+    assert_eq!(data.types_element_bytes, std::mem::size_of::<crate::elements::ElementId>());
+    assert_eq!(data.types_len_bytes, data.types_len_elements * data.types_element_bytes);
     assert_eq!(data.colors_len_bytes, data.colors_len_elements * std::mem::size_of::<u32>());
     assert_eq!(
         data.temperature_len_bytes,
         data.temperature_len_elements * std::mem::size_of::<f32>()
     );
 }
+
+#[test]
+fn snapshot_roundtrip_restores_grid_bodies_and_settings() {
+    let mut world = WorldCore::new(48, 40);
+    world.set_gravity(0.5, 2.0);
+    world.set_ambient_temperature(-5.0);
+    world.add_particles_in_radius(10, 10, 3, EL_SAND);
+    world.add_particles_in_radius(30, 10, 2, EL_WATER);
+    assert_ne!(world.spawn_rigid_body(24.0, 30.0, 6, 4, EL_STONE), 0);
+    for _ in 0..5 {
+        world.step();
+    }
+
+    let bytes = world.save_snapshot();
+
+    let mut restored = WorldCore::new(8, 8);
+    restored.load_snapshot(&bytes).expect("snapshot should load");
+
+    assert_eq!(restored.width(), 48);
+    assert_eq!(restored.height(), 40);
+    assert_eq!(restored.frame(), world.frame());
+    assert_eq!(restored.particle_count(), world.particle_count());
+    assert_eq!(restored.rng, world.rng);
+    assert_eq!(restored.gravity_x, 0.5);
+    assert_eq!(restored.gravity_y, 2.0);
+    assert_eq!(restored.get_ambient_temperature(), -5.0);
+    assert_eq!(restored.grid.types, world.grid.types);
+    assert_eq!(restored.grid.colors, world.grid.colors);
+    assert_eq!(restored.grid.life, world.grid.life);
+    assert_eq!(restored.grid.temperature, world.grid.temperature);
+    assert_eq!(restored.grid.vx, world.grid.vx);
+    assert_eq!(restored.grid.vy, world.grid.vy);
+    assert_eq!(restored.rigid_body_count(), 1);
+    assert_eq!(restored.chunks_x(), world.chunks_x());
+
+    // Both worlds must continue identically.
+    world.step();
+    restored.step();
+    assert_eq!(restored.grid.types, world.grid.types);
+    assert_eq!(restored.save_snapshot(), world.save_snapshot());
+}
+
+#[test]
+fn snapshot_rejects_corrupt_input_without_touching_world() {
+    let mut world = WorldCore::new(16, 16);
+    assert!(world.add_particle(3, 3, EL_STONE));
+    let bytes = world.save_snapshot();
+
+    let mut target = WorldCore::new(16, 16);
+    assert!(target.add_particle(5, 5, EL_SAND));
+
+    assert!(target.load_snapshot(&bytes[..bytes.len() - 1]).is_err());
+    assert!(target.load_snapshot(b"nope").is_err());
+
+    let mut future = bytes.clone();
+    future[4] = 0xFF;
+    assert!(target.load_snapshot(&future).is_err());
+
+    assert_eq!(target.grid.get_type(5, 5), EL_SAND);
+    assert_eq!(target.particle_count(), 1);
+}
+
+#[test]
+fn snapshot_remaps_element_ids_by_key_and_reports_missing() {
+    let mut world = WorldCore::new(16, 16);
+    assert!(world.add_particle(5, 5, EL_SAND));
+    assert!(world.add_particle(6, 5, EL_WATER));
+    assert!(world.add_particle(7, 5, EL_OIL));
+    let bytes = world.save_snapshot();
+
+    // Different bundle: water and sand get other ids, oil does not exist.
+    let mut target = WorldCore::new(16, 16);
+    target
+        .load_content_bundle_json(&test_bundle_json(&[
+            ("base:empty", "solid"),
+            ("base:water", "liquid"),
+            ("base:sand", "powder"),
+        ]))
+        .expect("bundle should parse");
+
+    let report = target.load_snapshot(&bytes).expect("snapshot should load");
+    assert_eq!(report.missing_elements, vec!["base:oil".to_string()]);
+    assert_eq!(report.cleared_cells, 1);
+
+    assert_eq!(target.grid.get_type(5, 5), 2);
+    assert_eq!(target.grid.get_type(6, 5), 1);
+    assert_eq!(target.grid.get_type(7, 5), EL_EMPTY);
+    assert_eq!(target.particle_count(), 2);
+}
+
+#[test]
+fn content_reload_migrates_particles_by_key() {
+    let mut world = WorldCore::new(16, 16);
+    world
+        .load_content_bundle_json(&test_bundle_json(&[
+            ("base:empty", "solid"),
+            ("base:stone", "solid"),
+            ("base:sand", "powder"),
+            ("mod:glass", "solid"),
+        ]))
+        .unwrap();
+    assert!(world.add_particle(2, 2, 1));
+    assert!(world.add_particle(3, 2, 2));
+    assert!(world.add_particle(4, 2, 3));
+    world.grid.set_temp(2, 2, 300.0);
+
+    // New bundle reorders ids and drops mod:glass.
+    let next = test_bundle_json(&[
+        ("base:empty", "solid"),
+        ("base:sand", "powder"),
+        ("base:stone", "solid"),
+    ]);
+    let removed = world.reload_content_bundle_json(&next, None).unwrap();
+    assert_eq!(removed, vec!["mod:glass".to_string()]);
+
+    assert_eq!(world.grid.get_type(2, 2), 2);
+    assert_eq!(world.grid.get_temp(2, 2), 300.0);
+    assert_eq!(world.grid.get_type(3, 2), 1);
+    assert_eq!(world.grid.get_type(4, 2), EL_EMPTY);
+    assert_eq!(world.particle_count(), 2);
+    let expected = world.content.color_with_variation(2, ((2 * 7 + 2 * 13) & 31) as u8);
+    assert_eq!(Some(world.grid.get_color(2, 2)), expected);
+
+    // Fallback keeps removed particles as a chosen element.
+    let mut world = WorldCore::new(16, 16);
+    assert!(world.add_particle(1, 1, EL_OIL));
+    let removed = world.reload_content_bundle_json(&next, Some("base:stone")).unwrap();
+    assert!(removed.contains(&"base:oil".to_string()));
+    assert_eq!(world.grid.get_type(1, 1), 2);
+    assert_eq!(world.particle_count(), 1);
+
+    assert!(world.reload_content_bundle_json(&next, Some("base:nope")).is_err());
+}
+
+#[test]
+fn undo_redo_groups_strokes_and_restores_cells() {
+    let mut world = WorldCore::new(32, 32);
+
+    // One stroke = two brush dabs = one undo step.
+    world.begin_stroke();
+    world.add_particles_in_radius(5, 5, 1, EL_SAND);
+    world.add_particles_in_radius(6, 5, 1, EL_SAND);
+    world.end_stroke();
+    let painted = world.particle_count();
+    assert!(painted > 5);
+
+    assert!(world.remove_particle(5, 5));
+    assert_eq!(world.particle_count(), painted - 1);
+
+    assert!(world.undo());
+    assert_eq!(world.grid.get_type(5, 5), EL_SAND);
+    assert_eq!(world.particle_count(), painted);
+
+    assert!(world.undo());
+    assert_eq!(world.particle_count(), 0);
+    assert_eq!(world.grid.get_type(6, 5), EL_EMPTY);
+    assert!(!world.can_undo());
+
+    assert!(world.redo());
+    assert_eq!(world.particle_count(), painted);
+    assert_eq!(world.grid.get_type(7, 5), EL_SAND);
+
+    // A new edit drops the redo branch.
+    world.add_particle(20, 20, EL_WATER);
+    assert!(!world.can_redo());
+
+    // Clear is undoable, including rigid bodies.
+    world.spawn_rigid_body(16.0, 25.0, 4, 2, EL_STONE);
+    let before = world.particle_count();
+    world.clear();
+    assert_eq!(world.particle_count(), 0);
+    assert!(world.undo());
+    assert_eq!(world.particle_count(), before);
+    assert_eq!(world.rigid_body_count(), 1);
+    assert_eq!(world.grid.get_type(20, 20), EL_WATER);
+}
+
+#[test]
+fn history_budget_evicts_oldest_steps() {
+    let mut world = WorldCore::new(32, 32);
+    world.add_particle(1, 1, EL_SAND);
+    world.add_particle(2, 1, EL_SAND);
+    world.add_particle(3, 1, EL_SAND);
+
+    // Room for roughly one single-cell step.
+    world.set_history_budget(100);
+    assert!(world.undo());
+    assert!(!world.undo());
+    assert_eq!(world.grid.get_type(3, 1), EL_EMPTY);
+    assert_eq!(world.grid.get_type(2, 1), EL_SAND);
+
+    // A step over the whole budget is dropped, not kept half-undoable
+    world.clear();
+    assert!(!world.can_undo());
+
+    world.set_history_budget(0);
+    assert!(!world.can_undo());
+    assert!(!world.can_redo());
+}
+
+#[test]
+fn recording_replays_to_identical_state() {
+    let mut world = WorldCore::new(48, 48);
+    world.add_particles_in_radius(10, 10, 3, EL_SAND);
+    world.step();
+
+    world.start_recording();
+    world.add_particles_in_radius(20, 5, 4, EL_WATER);
+    world.spawn_rigid_body(30.0, 10.0, 4, 3, EL_STONE);
+    for _ in 0..5 {
+        world.step();
+    }
+    world.set_gravity(0.5, 1.0);
+    world.begin_stroke();
+    world.add_particles_in_radius(8, 30, 2, EL_LAVA);
+    world.remove_particles_in_radius(10, 10, 1);
+    world.end_stroke();
+    for _ in 0..10 {
+        world.step();
+    }
+    world.undo();
+    world.step();
+    let recording = world.stop_recording().unwrap();
+    assert!(!world.is_recording());
+
+    // Replays onto a world in any state (the start snapshot replaces it).
+    let mut replayed = WorldCore::new(8, 8);
+    replayed.add_particle(1, 1, EL_OIL);
+    let report = replayed.replay_recording(&recording).unwrap();
+    assert_eq!(report.final_frame, world.frame());
+    assert_eq!(replayed.save_snapshot(), world.save_snapshot());
+
+    // Tampered final hash is detected.
+    let mut bad = recording.clone();
+    let last = bad.len() - 1;
+    bad[last] ^= 0xFF;
+    let err = WorldCore::new(8, 8).replay_recording(&bad).unwrap_err();
+    assert!(err.contains("diverged"), "{}", err);
+}
+
+#[test]
+fn state_hash_matches_identical_runs_and_locates_divergence() {
+    let run = || {
+        let mut world = WorldCore::new(70, 40);
+        world.add_particles_in_radius(10, 5, 3, EL_SAND);
+        world.add_particles_in_radius(50, 5, 3, EL_WATER);
+        for _ in 0..20 {
+            world.step();
+        }
+        world
+    };
+    let a = run();
+    let mut b = run();
+    assert_eq!(a.state_hash(), b.state_hash());
+    assert_eq!(a.chunk_hashes(), b.chunk_hashes());
+    // 70x40 -> 3x2 chunks
+    assert_eq!(a.chunk_hashes().len(), 6);
+
+    b.grid.set_temp(40, 35, 99.0);
+    assert_ne!(a.state_hash(), b.state_hash());
+    let diverged: Vec<usize> = a
+        .chunk_hashes()
+        .iter()
+        .zip(b.chunk_hashes().iter())
+        .enumerate()
+        .filter(|(_, (x, y))| x != y)
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(diverged, vec![3 + 1]);
+
+    // RNG state is part of the hash even when the grid matches.
+    let mut c = run();
+    c.rng.reactions ^= 1;
+    assert_eq!(a.chunk_hashes(), c.chunk_hashes());
+    assert_ne!(a.state_hash(), c.state_hash());
+}
+
+#[test]
+fn seeded_worlds_reproduce_and_rng_streams_are_independent() {
+    let run = |seed: u64, reaction_draws: u32| {
+        let mut world = WorldCore::new(64, 64);
+        world.set_seed(seed);
+        for _ in 0..reaction_draws {
+            xorshift32(&mut world.rng.reactions);
+        }
+        world.add_particles_in_radius(20, 10, 6, EL_SAND);
+        world.add_particles_in_radius(44, 10, 6, EL_WATER);
+        for _ in 0..30 {
+            world.step();
+        }
+        world
+    };
+
+    let a = run(7, 0);
+    assert_eq!(a.seed(), 7);
+    assert_eq!(a.chunk_hashes(), run(7, 0).chunk_hashes());
+    assert_ne!(a.chunk_hashes(), run(8, 0).chunk_hashes());
+
+    // Extra reaction rolls must not change how sand and water move.
+    let b = run(7, 13);
+    assert_eq!(a.grid.types, b.grid.types);
+    assert_ne!(a.rng.reactions, b.rng.reactions);
+    assert_eq!(a.rng.behaviors, b.rng.behaviors);
+
+    // Streams survive a snapshot roundtrip.
+    let mut restored = WorldCore::new(4, 4);
+    restored.load_snapshot(&a.save_snapshot()).unwrap();
+    assert_eq!(restored.rng, a.rng);
+}
+
+#[test]
+fn resize_keeps_overlap_and_shifts_bodies() {
+    let mut world = WorldCore::new(40, 40);
+    world.add_particle(0, 0, EL_STONE);
+    world.add_particle(39, 39, EL_WATER);
+    world.grid.set_temp(39, 39, 55.0);
+    world.spawn_rigid_body(20.0, 20.0, 4, 2, EL_STONE);
+    let body_cells = world.rigid_bodies.bodies()[0].prev_world_coords.len() as u32;
+
+    // Grow around the center: everything moves by (10, 20).
+    world.resize(60, 80, ResizeAnchor::Center).unwrap();
+    assert_eq!((world.width(), world.height()), (60, 80));
+    assert_eq!(world.chunks_x(), 2);
+    assert_eq!(world.chunks_y(), 3);
+    assert_eq!(world.grid.get_type(10, 20), EL_STONE);
+    assert_eq!(world.grid.get_type(49, 59), EL_WATER);
+    assert_eq!(world.grid.get_temp(49, 59), 55.0);
+    assert_eq!(world.particle_count(), 2 + body_cells);
+    let body = &world.rigid_bodies.bodies()[0];
+    assert_eq!((body.pos.x, body.pos.y), (30.0, 40.0));
+    assert!(body.prev_world_coords.iter().all(|&(x, y)| world.grid.get_type(x, y) == EL_STONE));
+
+    // Shrink anchored bottom-right: the top-left stone and the body are cut off.
+    world.resize(20, 30, ResizeAnchor::BottomRight).unwrap();
+    assert_eq!(world.grid.get_type(9, 9), EL_WATER);
+    assert_eq!(world.particle_count(), 1);
+    assert_eq!(world.rigid_body_count(), 0);
+
+    assert!(world.resize(0, 10, ResizeAnchor::TopLeft).is_err());
+    assert!(world.resize(65536, 65535, ResizeAnchor::TopLeft).is_err());
+    assert!(world.load_scene(r#"{ "formatVersion": 1, "width": 100000, "height": 100000 }"#).is_err());
+    assert_eq!((world.width(), world.height()), (20, 30));
+    world.step();
+}
+
+#[test]
+fn stamps_copy_transform_paste_and_roundtrip() {
+    let mut world = WorldCore::new(32, 32);
+    // 3x2 region: S S W / . . S
+    world.add_particle(1, 1, EL_SAND);
+    world.add_particle(2, 1, EL_SAND);
+    world.add_particle(3, 1, EL_WATER);
+    world.add_particle(3, 2, EL_STONE);
+    let idx = world.grid.index(3, 1);
+    world.grid.vx[idx] = 2.0;
+
+    let stamp = world.copy_region(1, 1, 3, 2).unwrap();
+    assert_eq!((stamp.width(), stamp.height()), (3, 2));
+    assert_eq!(stamp.particle_count(), 4);
+
+    // Oversized regions are rejected before allocating; far-off corners do not overflow.
+    assert!(world.copy_region(0, 0, u32::MAX, 1).is_err());
+    assert_eq!(world.copy_region(i32::MAX, i32::MAX, 32, 32).unwrap().particle_count(), 0);
+    assert_eq!(world.paste_stamp(&stamp, i32::MAX, 0, 0, false, StampBlend::Overwrite), 0);
+
+    // One clockwise turn: 2x3, top-right corner holds the old top-left.
+    let turned = stamp.transformed(1, false);
+    assert_eq!((turned.width(), turned.height()), (2, 3));
+    assert_eq!(turned.element_at(1, 0), Some(EL_SAND));
+    assert_eq!(turned.element_at(1, 2), Some(EL_WATER));
+    assert_eq!(turned.element_at(0, 2), Some(EL_STONE));
+    assert_eq!(stamp.transformed(1, false).transformed(3, false), stamp);
+    assert_eq!(stamp.transformed(0, true).transformed(0, true), stamp);
+    assert_eq!(stamp.transformed(2, false).transformed(2, false), stamp);
+
+    // Mirror then turn: W S S / T . .  ->  T W / . S / . S
+    // Water moving right moves left after the mirror, then up after the turn.
+    let placed = world.paste_stamp(&stamp, 10, 10, 1, true, StampBlend::Overwrite);
+    assert_eq!(placed, 4);
+    assert_eq!(world.particle_count(), 8);
+    assert_eq!(world.grid.get_type(10, 10), EL_STONE);
+    assert_eq!(world.grid.get_type(11, 10), EL_WATER);
+    assert_eq!(world.grid.get_type(11, 12), EL_SAND);
+    assert_eq!(world.grid.vy[world.grid.index(11, 10)], -2.0);
+
+    // Into-empty keeps existing cells, skip-empty-source keeps cells under holes.
+    world.add_particle(20, 21, EL_OIL);
+    world.add_particle(20, 20, EL_OIL);
+    assert_eq!(world.paste_stamp(&stamp, 20, 20, 0, false, StampBlend::IntoEmpty), 3);
+    assert_eq!(world.grid.get_type(20, 20), EL_OIL);
+    assert_eq!(world.paste_stamp(&stamp, 20, 20, 0, false, StampBlend::SkipEmptySource), 4);
+    assert_eq!(world.grid.get_type(20, 20), EL_SAND);
+    assert_eq!(world.grid.get_type(20, 21), EL_OIL);
+    assert!(world.undo());
+    assert_eq!(world.grid.get_type(20, 20), EL_OIL);
+
+    // Cut clears the source; the bytes survive a content reorder.
+    let before = world.particle_count();
+    let cut = world.cut_region(1, 1, 3, 2).unwrap();
+    assert_eq!(world.particle_count(), before - 4);
+    let bytes = world.encode_stamp(&cut);
+    let decoded = world.decode_stamp(&bytes).unwrap();
+    assert_eq!(decoded, cut);
+
+    let mut other = WorldCore::new(8, 8);
+    other
+        .load_content_bundle_json(&test_bundle_json(&[("base:empty", "solid"), ("base:water", "liquid")]))
+        .unwrap();
+    let remapped = other.decode_stamp(&bytes).unwrap();
+    assert_eq!(remapped.element_at(2, 0), Some(1));
+    assert_eq!(remapped.particle_count(), 1);
+    assert!(other.decode_stamp(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn image_import_matches_palette_and_alpha() {
+    let mut world = WorldCore::new(16, 16);
+    let rgb = |id: ElementId| {
+        let c = world.content.props(id).unwrap().color;
+        [(c >> 16) as u8, (c >> 8) as u8, c as u8]
+    };
+    let [sr, sg, sb] = rgb(EL_SAND);
+    let [wr, wg, wb] = rgb(EL_WATER);
+    // 3x1: sand-ish, transparent, water
+    let image = [
+        sr.saturating_add(3), sg, sb, 255,
+        255, 0, 0, 10,
+        wr, wg, wb.saturating_sub(2), 200,
+    ];
+
+    world.add_particle(6, 4, EL_STONE);
+    let mut options = ImageImportOptions::new();
+    options.set_palette(vec![EL_SAND, EL_WATER]);
+    let placed = world.import_image(&image, 3, 1, 5, 4, &options).unwrap();
+    assert_eq!(placed, 2);
+    assert_eq!(world.grid.get_type(5, 4), EL_SAND);
+    assert_eq!(world.grid.get_type(6, 4), EL_EMPTY);
+    assert_eq!(world.grid.get_type(7, 4), EL_WATER);
+    assert_eq!(world.particle_count(), 2);
+
+    // One undo step restores the overwritten stone.
+    assert!(world.undo());
+    assert_eq!(world.grid.get_type(6, 4), EL_STONE);
+    assert_eq!(world.particle_count(), 1);
+
+    // Clipped at the edge; a low threshold keeps faint pixels.
+    options.set_alpha_threshold(5);
+    assert_eq!(world.import_image(&image, 3, 1, 14, 0, &options).unwrap(), 2);
+
+    assert_eq!(world.import_image(&image, 3, 1, i32::MAX - 1, 0, &options).unwrap(), 0);
+    assert!(world.import_image(&image, 2, 2, 0, 0, &options).is_err());
+    options.set_palette(vec![EL_EMPTY]);
+    assert!(world.import_image(&image, 3, 1, 0, 0, &options).is_err());
+}
+
+#[test]
+fn headless_renderer_produces_rgba_views() {
+    let mut world = WorldCore::new(4, 2);
+    world.add_particle(0, 0, EL_WATER);
+    world.add_particle(1, 0, EL_LAVA);
+    let idx = world.grid.index(0, 0);
+    world.grid.vx[idx] = 10.0;
+
+    let normal = world.render_rgba(RenderView::Normal);
+    assert_eq!(normal.len(), 4 * 2 * 4);
+    assert_eq!(&normal[0..4], &world.grid.colors[0].to_le_bytes());
+    assert_eq!(&normal[8..12], &[10, 10, 10, 255]);
+
+    // Lava is hot (red-ish), empty air at ambient 20 is green/cyan.
+    let thermal = world.render_rgba(RenderView::Thermal);
+    assert_eq!(thermal[4], 255);
+    assert_eq!(&thermal[8..12], &[0, 255, 255, 255]);
+
+    // Full-speed rightward motion = pure red.
+    let velocity = world.render_rgba(RenderView::Velocity);
+    assert_eq!(&velocity[0..4], &[255, 0, 0, 255]);
+    assert_eq!(&velocity[4..8], &[0, 0, 0, 255]);
+
+    world.add_particle(3, 1, EL_SAND);
+    let category = world.render_rgba(RenderView::Category);
+    assert_eq!(&category[0..4], &category[4..8]);
+    assert_ne!(&category[28..32], &category[0..4]);
+    assert_eq!(&category[8..12], &[10, 10, 10, 255]);
+}
+
+#[test]
+fn brush_shapes_and_placement_modes() {
+    let mut world = WorldCore::new(20, 20);
+
+    // 3x3 square, fill-empty skips occupied cells.
+    world.add_particle(5, 5, EL_WATER);
+    let square = BrushShape::Square { cx: 5, cy: 5, half_size: 1 };
+    assert_eq!(world.paint(&square, &Brush::new(EL_SAND)).unwrap(), 8);
+    assert_eq!(world.grid.get_type(5, 5), EL_WATER);
+
+    // Replace only water, then a category-wide eraser.
+    let replace_water = Brush::with_mode(EL_STONE, BrushMode::ReplaceElement(EL_WATER));
+    assert_eq!(world.paint(&square, &replace_water).unwrap(), 1);
+    assert_eq!(world.grid.get_type(5, 5), EL_STONE);
+    let erase_powders = Brush::with_mode(EL_EMPTY, BrushMode::ReplaceCategory(crate::elements::CAT_POWDER));
+    assert_eq!(world.paint(&square, &erase_powders).unwrap(), 8);
+    assert_eq!(world.particle_count(), 1);
+
+    // Horizontal line 3 cells thick overwrites the stone.
+    let line = BrushShape::Line { x0: 2, y0: 10, x1: 12, y1: 10, thickness: 3 };
+    world.add_particle(7, 10, EL_STONE);
+    let wall = Brush::with_mode(EL_WATER, BrushMode::ReplaceAll);
+    world.paint(&line, &wall).unwrap();
+    assert_eq!(world.grid.get_type(7, 10), EL_WATER);
+    assert_eq!(world.grid.get_type(7, 11), EL_WATER);
+    assert_eq!(world.grid.get_type(7, 12), EL_EMPTY);
+    assert_eq!(world.grid.get_type(13, 10), EL_WATER);
+
+    // Extreme endpoints and thickness are clipped to the grid, not walked.
+    let huge = BrushShape::Line { x0: i32::MIN, y0: i32::MIN, x1: i32::MAX, y1: i32::MAX, thickness: u32::MAX };
+    assert_eq!(huge.cells(20, 20).unwrap().len(), 400);
+    let far = BrushShape::Line { x0: i32::MIN, y0: 5, x1: i32::MAX, y1: 5, thickness: 1 };
+    assert_eq!(far.cells(20, 20).unwrap().len(), 20);
+
+    // Ring leaves the center hollow; clipped at the border.
+    let ring = BrushShape::Ring { cx: 0, cy: 0, radius: 4, thickness: 1 };
+    let cells = ring.cells(20, 20).unwrap();
+    assert!(cells.contains(&(4, 0)) && cells.contains(&(0, 4)));
+    assert!(!cells.contains(&(0, 0)) && !cells.contains(&(2, 2)));
+
+    // Right triangle sampled at cell centers: rows shrink towards the apex.
+    let triangle = BrushShape::Polygon { points: vec![(0, 14), (6, 20), (0, 20)] };
+    let cells = triangle.cells(20, 20).unwrap();
+    assert_eq!(cells.iter().filter(|c| c.1 == 19).count(), 5);
+    assert_eq!(cells.iter().filter(|c| c.1 == 15).count(), 1);
+    assert_eq!(cells.iter().filter(|c| c.1 == 14).count(), 0);
+    assert!(BrushShape::Polygon { points: vec![(0, 0), (1, 1)] }.cells(20, 20).is_err());
+
+    // Each paint call is one undo step.
+    assert!(world.undo());
+    assert_eq!(world.grid.get_type(7, 10), EL_STONE);
+    assert!(world.paint(&square, &Brush::new(200)).is_err());
+}
+
+#[test]
+fn flood_fill_respects_connectivity_and_limit() {
+    let mut world = WorldCore::new(10, 10);
+    // Stone box (x/y 1..=7) missing its top-left corner: only a diagonal gap.
+    let wall = Brush::with_mode(EL_STONE, BrushMode::FillEmpty);
+    world.paint(&BrushShape::Square { cx: 4, cy: 4, half_size: 3 }, &wall).unwrap();
+    world.paint(&BrushShape::Square { cx: 4, cy: 4, half_size: 2 }, &Brush::with_mode(EL_EMPTY, BrushMode::ReplaceAll)).unwrap();
+    world.remove_particle(1, 1);
+
+    // 4-connected: the 5x5 interior stays inside the walls.
+    assert_eq!(world.flood_fill(4, 4, EL_WATER, 4, 1000).unwrap(), 25);
+    assert_eq!(world.grid.get_type(0, 0), EL_EMPTY);
+    assert!(world.undo());
+
+    // 8-connected leaks through the diagonal gap into the outside.
+    assert!(world.flood_fill(4, 4, EL_WATER, 8, 30).is_err());
+    assert_eq!(world.grid.get_type(4, 4), EL_EMPTY);
+    assert_eq!(world.flood_fill(4, 4, EL_WATER, 8, 1000).unwrap(), 100 - 23);
+
+    // Filling a solid region replaces it; same element is a no-op.
+    assert_eq!(world.flood_fill(7, 7, EL_SAND, 4, 1000).unwrap(), 23);
+    assert_eq!(world.grid.get_type(1, 2), EL_SAND);
+    assert_eq!(world.flood_fill(4, 4, EL_WATER, 4, 1000).unwrap(), 0);
+    assert!(world.flood_fill(4, 4, EL_WATER, 6, 1000).is_err());
+}
+
+#[test]
+fn temperature_brush_heats_air_and_triggers_phase_change() {
+    let mut world = WorldCore::new(16, 16);
+    world.add_particle(8, 8, EL_ICE);
+
+    // Falloff: full delta at the center, less towards the rim, untouched outside.
+    world.apply_heat_in_radius(8, 8, 3, 40.0, true);
+    let near = world.grid.get_temp(9, 8);
+    let rim = world.grid.get_temp(11, 8);
+    assert!(near > rim && rim > world.grid.get_temp(12, 8));
+    assert!(world.undo());
+
+    // Uniform heat reaches air too; the ice melts on the next step.
+    world.set_temperature_in_radius(8, 8, 2, 60.0);
+    assert_eq!(world.grid.get_temp(6, 8), 60.0);
+    assert_eq!(world.grid.get_type(8, 8), EL_ICE);
+    world.step();
+    assert_eq!(world.grid.get_type(8, 8), EL_WATER);
+
+    // Cooling is clamped at absolute zero.
+    world.apply_heat_in_radius(0, 0, 0, -1.0e6, false);
+    assert_eq!(world.grid.get_temp(0, 0), -273.15);
+}
+
+#[test]
+fn velocity_tools_push_movable_particles() {
+    let mut world = WorldCore::new(32, 32);
+    world.add_particle(16, 16, EL_SAND);
+    world.add_particle(18, 16, EL_SAND);
+    world.add_particle(16, 18, EL_SAND);
+    world.add_particle(14, 16, EL_STONE);
+    world.add_particle(16, 14, EL_STEAM);
+
+    // Stone is pinned and steam moves by gas behavior, so both are skipped.
+    assert_eq!(world.apply_impulse_in_radius(16, 16, 3, 4.0, -2.0), 3);
+    let idx = world.grid.index(16, 16);
+    assert_eq!((world.grid.vx[idx], world.grid.vy[idx]), (4.0, -2.0));
+    let steam = world.grid.index(16, 14);
+    assert_eq!((world.grid.vx[steam], world.grid.vy[steam]), (0.0, 0.0));
+    assert!(world.undo());
+
+    // Blast: outward, the center has no direction.
+    world.apply_blast(16, 16, 4, 5.0);
+    let right = world.grid.index(18, 16);
+    let below = world.grid.index(16, 18);
+    assert!(world.grid.vx[right] > 0.0 && world.grid.vy[right] == 0.0);
+    assert!(world.grid.vy[below] > 0.0 && world.grid.vx[below] == 0.0);
+    assert_eq!(world.grid.vx[idx], 0.0);
+    assert!(world.undo());
+
+    // Vortex: clockwise on screen, so right goes down and below goes left.
+    world.apply_vortex(16, 16, 4, 5.0);
+    assert!(world.grid.vy[right] > 0.0);
+    assert!(world.grid.vx[below] < 0.0);
+
+    // Velocity is carried by the physics pass and clamped.
+    world.apply_impulse_in_radius(18, 16, 0, 100.0, 0.0);
+    assert_eq!(world.grid.vx[right], crate::elements::MAX_VELOCITY);
+    world.set_gravity(0.0, 0.0);
+    world.step();
+    assert_eq!(world.grid.get_type(18, 16), EL_EMPTY);
+}
+
+#[test]
+fn inspect_cell_and_region_stats() {
+    let mut world = WorldCore::new(16, 16);
+    world.add_particle(2, 3, EL_WATER);
+    world.add_particle(3, 3, EL_WATER);
+    world.add_particle(4, 3, EL_LAVA);
+    let idx = world.grid.index(2, 3);
+    world.grid.vx[idx] = 1.5;
+    let body = world.spawn_rigid_body(10.0, 10.0, 2, 2, EL_STONE);
+
+    let cell = world.inspect_cell(2, 3).unwrap();
+    assert_eq!((cell.element(), cell.key().as_str()), (EL_WATER, "base:water"));
+    assert_eq!(cell.vx(), 1.5);
+    assert_eq!(cell.rigid_body(), None);
+    assert!(world.inspect_cell(-1, 0).is_none());
+    assert!(world.inspect_cell(16, 0).is_none());
+    let stone = (0..16)
+        .flat_map(|y| (0..16).map(move |x| (x, y)))
+        .find(|&(x, y)| world.grid.get_type(x, y) == EL_STONE)
+        .unwrap();
+    assert_eq!(world.inspect_cell(stone.0, stone.1).unwrap().rigid_body(), Some(body));
+
+    // Clipped at the top-left corner: 6x6 in-bounds cells.
+    let stats = world.region_stats(-2, -2, 8, 8).unwrap();
+    assert_eq!(stats.cells(), 36);
+    assert_eq!(stats.count(EL_WATER), 2);
+    assert_eq!(stats.count(EL_EMPTY), 33);
+    assert_eq!(stats.element_ids(), vec![EL_EMPTY, EL_WATER, EL_LAVA]);
+    assert_eq!(stats.element_counts(), vec![33, 2, 1]);
+    assert_eq!(stats.max_temperature(), world.grid.get_temp(4, 3));
+    assert!(stats.avg_temperature() > stats.min_temperature());
+
+    assert!(world.region_stats(0, 0, 0, 4).is_err());
+    assert!(world.region_stats(20, 20, 4, 4).is_err());
+}
+
+#[test]
+fn command_batches_run_in_order_with_results() {
+    let encode = |cmds: &[RecordedCommand]| {
+        let mut w = snapshot::codec::ByteWriter::with_capacity(64);
+        for cmd in cmds {
+            cmd.encode(&mut w);
+        }
+        w.into_inner()
+    };
+    let mut world = WorldCore::new(16, 16);
+
+    let batch = encode(&[
+        RecordedCommand::BeginStroke,
+        RecordedCommand::AddParticle { x: 1, y: 1, element: EL_SAND },
+        RecordedCommand::AddParticle { x: 1, y: 1, element: EL_SAND },
+        RecordedCommand::Paint {
+            shape: BrushShape::Line { x0: 0, y0: 8, x1: 15, y1: 8, thickness: 1 },
+            element: EL_STONE,
+            mode: 0,
+            target: 0,
+        },
+        RecordedCommand::FloodFill { x: 0, y: 0, element: EL_WATER, connectivity: 4, max_cells: 10 },
+        RecordedCommand::ApplyImpulse { cx: 1, cy: 1, radius: 0, vx: 2.0, vy: 0.0 },
+        RecordedCommand::SetTemperatureInRadius { cx: 5, cy: 5, radius: 0, temp: 90.0 },
+        RecordedCommand::EndStroke,
+    ]);
+    let results = world.apply_commands(&batch).unwrap();
+    assert_eq!(results, vec![0, 1, 0, 16, -1, 1, 0, 0]);
+    assert_eq!(world.grid.get_type(7, 8), EL_STONE);
+    assert_eq!(world.grid.get_temp(5, 5), 90.0);
+
+    // The explicit stroke makes the whole batch one undo step.
+    assert!(world.undo());
+    assert_eq!(world.particle_count(), 0);
+    assert!(!world.can_undo());
+
+    // Truncated or non-edit commands reject the whole batch before running any of it.
+    let mut bad = encode(&[RecordedCommand::AddParticle { x: 2, y: 2, element: EL_SAND }, RecordedCommand::Clear]);
+    assert!(world.apply_commands(&bad).is_err());
+    bad.truncate(5);
+    assert!(world.apply_commands(&bad).is_err());
+    assert_eq!(world.particle_count(), 0);
+    assert_eq!(world.apply_commands(&[]).unwrap(), Vec::<i32>::new());
+}
+
+#[test]
+fn terrain_generator_is_seeded_and_layered() {
+    let generate = |seed: u64| {
+        let mut world = WorldCore::new(128, 96);
+        let placed = world.generate_terrain(&TerrainConfig::new(seed)).unwrap();
+        assert_eq!(placed, world.particle_count());
+        world
+    };
+    let a = generate(7);
+    let b = generate(7);
+    assert_eq!(a.grid.types, b.grid.types);
+    assert_ne!(a.grid.types, generate(8).grid.types);
+
+    let count = |id| a.grid.types.iter().filter(|&&t| t == id).count();
+    for id in [EL_STONE, crate::elements::EL_DIRT, EL_WATER, EL_LAVA] {
+        assert!(count(id) > 0, "missing element {}", id);
+    }
+    assert!(count(crate::elements::EL_PLANT) + count(crate::elements::EL_SEED) > 0);
+    // Sky on top, solid ground at the bottom (lava pockets aside).
+    assert!((0..128).all(|x| a.grid.get_type(x, 0) == EL_EMPTY));
+    assert!((0..128).filter(|&x| a.grid.get_type(x, 95) == EL_EMPTY).count() < 64);
+
+    // Generation replaces the scene as one undo step.
+    let mut world = WorldCore::new(64, 64);
+    world.add_particle(0, 0, EL_SAND);
+    let mut config = TerrainConfig::new(3);
+    config.water_pools = 0;
+    config.lava_pockets = 0;
+    world.generate_terrain(&config).unwrap();
+    assert_eq!(world.grid.get_type(0, 0), EL_EMPTY);
+    assert!(world.undo());
+    assert_eq!(world.particle_count(), 1);
+
+    // A dirt cap deeper than the world is all dirt, not an overflow
+    config.dirt_depth = u32::MAX;
+    config.strata = 0.0;
+    config.vegetation = 0.0;
+    world.generate_terrain(&config).unwrap();
+    assert!(world.grid.types.iter().all(|&t| t == EL_EMPTY || t == config.dirt_element));
+    config.lava_element = 200;
+    assert!(world.generate_terrain(&config).is_err());
+}
+
+#[test]
+fn scene_files_resolve_keys_and_build_the_world() {
+    let json = r#"{
+        "formatVersion": 1,
+        "width": 40, "height": 30,
+        "seed": 99,
+        "gravity": { "x": 0, "y": -1 },
+        "ambientTemperature": 5,
+        "shapes": [
+            { "type": "rect", "element": "base:stone", "x": 0, "y": 25, "w": 40, "h": 5 },
+            { "type": "circle", "element": "base:water", "cx": 10, "cy": 25, "radius": 3, "mode": "fillEmpty" },
+            { "type": "line", "element": "base:sand", "x0": 20, "y0": 10, "x1": 30, "y1": 10 },
+            { "type": "rect", "element": "base:lava", "x": 35, "y": 0, "w": 2, "h": 2, "temperature": 1500 }
+        ],
+        "rigidBodies": [
+            { "type": "box", "element": "base:stone", "x": 5, "y": 5, "w": 3, "h": 3 },
+            { "type": "box", "element": "base:stone", "x": 5, "y": 5, "w": 3, "h": 3 }
+        ]
+    }"#;
+    let mut world = WorldCore::new(16, 16);
+    world.add_particle(0, 0, EL_SAND);
+    let report = world.load_scene(json).unwrap();
+
+    assert_eq!((world.width(), world.height()), (40, 30));
+    assert_eq!(world.seed(), 99);
+    assert_eq!(world.gravity_y, -1.0);
+    assert_eq!(world.grid.get_type(0, 0), EL_EMPTY);
+    assert_eq!(world.grid.get_temp(0, 1), 5.0);
+    // fillEmpty keeps the stone floor under the water circle.
+    assert_eq!(world.grid.get_type(10, 25), EL_STONE);
+    assert_eq!(world.grid.get_type(10, 23), EL_WATER);
+    assert_eq!(world.grid.get_type(39, 29), EL_STONE);
+    assert_eq!(world.grid.get_type(25, 10), EL_SAND);
+    assert_eq!(world.grid.get_temp(36, 1), 1500.0);
+    // Second body overlaps the first and cannot be placed.
+    assert!(report.rigid_bodies[0] > 0);
+    assert_eq!(report.rigid_bodies[1], 0);
+    assert_eq!(report.particles, world.particle_count());
+    assert!(!world.can_undo());
+
+    // Unknown keys and bad versions leave the world untouched.
+    let before = world.state_hash();
+    assert!(world.load_scene(&json.replace("base:sand", "mod:nope")).unwrap_err().contains("mod:nope"));
+    assert!(world.load_scene(&json.replace("\"formatVersion\": 1", "\"formatVersion\": 9")).is_err());
+    assert!(world.load_scene(r#"{ "formatVersion": 1, "width": 4, "height": 4, "bogus": 1 }"#).is_err());
+    // 1e39 is infinite as f32
+    assert!(world.load_scene(&json.replace("\"ambientTemperature\": 5", "\"ambientTemperature\": 1e39")).is_err());
+    assert_eq!(world.state_hash(), before);
+}
+
+#[test]
+fn element_property_overrides_copy_on_write_and_reset() {
+    let mut world = WorldCore::new(16, 16);
+    assert!(world.add_particle(2, 2, EL_SAND));
+    let loaded = Arc::clone(&world.content);
+    let sand = *loaded.props(EL_SAND).unwrap();
+
+    world.set_element_property(EL_SAND, "density", 0.5).unwrap();
+    world.set_element_property(EL_SAND, "heatConductivity", 200.0).unwrap();
+    world.set_element_property(EL_SAND, "color", 0xFF00_FF00u32 as f64).unwrap();
+    assert_eq!(world.content.props(EL_SAND).unwrap().density, 0.5);
+    assert_eq!(world.content.props(EL_SAND).unwrap().heat_conductivity, 200);
+    // The registry shared before the edit is untouched.
+    assert_eq!(loaded.props(EL_SAND).unwrap().density, sand.density);
+    let expected = world.content.color_with_variation(EL_SAND, ((2 * 7 + 2 * 13) & 31) as u8);
+    assert_eq!(Some(world.grid.get_color(2, 2)), expected);
+
+    // Values are checked against the bundle field types.
+    assert!(world.set_element_property(EL_SAND, "dispersion", 256.0).is_err());
+    assert!(world.set_element_property(EL_SAND, "dispersion", 1.5).is_err());
+    assert!(world.set_element_property(EL_SAND, "color", -1.0).is_err());
+    assert!(world.set_element_property(EL_SAND, "bounce", f64::NAN).is_err());
+    assert!(world.set_element_property(EL_SAND, "lifetime", 10.0).is_err());
+    assert!(world.set_element_property(200, "density", 1.0).is_err());
+
+    world.reset_element_properties();
+    assert!(Arc::ptr_eq(&world.content, &loaded));
+    let expected = loaded.color_with_variation(EL_SAND, ((2 * 7 + 2 * 13) & 31) as u8);
+    assert_eq!(Some(world.grid.get_color(2, 2)), expected);
+}
+
+#[test]
+fn content_validation_reports_every_problem() {
+    use crate::domain::content_report::{ContentError, ContentWarning};
+
+    let json = test_bundle_json(&[
+        ("base:empty", "utility"),
+        ("mod:rock", "solid"),
+        ("mod:goo", "slime"),
+        ("mod:ghost", "gas"),
+    ]);
+    let mut bundle: serde_json::Value = serde_json::from_str(&json).unwrap();
+    bundle["elements"][1]["phaseChange"] = serde_json::json!({ "high": { "temp": 900.0, "toId": 42 } });
+    bundle["elements"][3]["hidden"] = serde_json::json!(true);
+    bundle["reactions"] = serde_json::json!([
+        { "aggressorId": 1, "victimId": 2, "resultAggressorId": null, "resultVictimId": 9, "spawnId": null, "chance": 0.5 },
+        { "aggressorId": 2, "victimId": 1, "resultAggressorId": null, "resultVictimId": 0, "spawnId": null, "chance": 0.0 },
+        { "aggressorId": 2, "victimId": 2, "resultAggressorId": null, "resultVictimId": 0, "spawnId": null, "chance": 1.5 }
+    ]);
+    let report = ContentRegistry::validate_bundle_json(&bundle.to_string());
+
+    assert_eq!(
+        report.errors,
+        vec![
+            ContentError::UnknownCategory { element: "mod:goo".into(), category: "slime".into() },
+            ContentError::UnknownPhaseTarget { element: "mod:rock".into(), field: "high", id: 42 },
+            ContentError::UnknownReactionElement { reaction: "mod:rock + mod:goo".into(), field: "resultVictimId", id: 9 },
+            ContentError::InvalidReactionChance { reaction: "mod:goo + mod:goo".into(), chance: 1.5 },
+        ]
+    );
+    assert_eq!(
+        report.warnings,
+        vec![
+            ContentWarning::ZeroChanceReaction { reaction: "mod:goo + mod:rock".into() },
+            ContentWarning::UnreachableElement { element: "mod:ghost".into() },
+        ]
+    );
+    assert!(report.to_json().contains(r#""kind":"unknownPhaseTarget""#));
+
+    // The String path names the first problem and counts the rest.
+    let err = ContentRegistry::from_bundle_json(&bundle.to_string()).err().unwrap();
+    assert_eq!(err, "element mod:goo: unknown category: slime (and 3 more errors)");
+
+    let report = ContentRegistry::validate_bundle_json("{");
+    assert!(matches!(report.errors.as_slice(), [ContentError::Parse { .. }]));
+    let clean = test_bundle_json(&[("base:empty", "utility"), ("mod:rock", "solid")]);
+    assert!(ContentRegistry::validate_bundle_json(&clean).is_ok());
+}
+
+#[test]
+fn reaction_table_is_sparse_and_reserved_element_id_is_rejected() {
+    use crate::domain::content_report::ContentError;
+    use crate::elements::{ElementId, Reaction, ReactionSystem, REACTION_INIT_DATA};
+
+    let mut table = ReactionSystem::default();
+    assert!(table.is_empty());
+    let melt = Reaction { target_becomes: EL_WATER, source_becomes: Reaction::NO_CHANGE, chance: 10, spawn: 0, ..REACTION_INIT_DATA[0].2 };
+    table.add(EL_LAVA, EL_ICE, melt);
+    table.add(EL_LAVA, EL_SAND, melt);
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.get(EL_LAVA, EL_ICE).len(), 1);
+    assert!(table.get(EL_ICE, EL_LAVA).is_empty());
+    assert!(table.get(Reaction::NO_CHANGE - 1, EL_SAND).is_empty());
+    assert_eq!(ReactionSystem::new().len(), REACTION_INIT_DATA.len());
+
+    let json = test_bundle_json(&[("base:empty", "utility"), ("mod:rock", "solid")]);
+    let mut bundle: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let reserved = u32::from(Reaction::NO_CHANGE);
+    bundle["elements"][1]["id"] = serde_json::json!(reserved);
+    bundle["elementKeyToId"]["mod:rock"] = serde_json::json!(reserved);
+    let report = ContentRegistry::validate_bundle_json(&bundle.to_string());
+    assert!(matches!(
+        report.errors.first(),
+        Some(ContentError::ElementIdOutOfRange { element, id }) if element == "mod:rock" && u32::from(*id) == reserved
+    ));
+
+    // The grid view exposes one `ElementId` per cell
+    let world = WorldCore::new(4, 4);
+    assert_eq!(world.types_byte_len(), 16 * std::mem::size_of::<ElementId>());
+}
+
+#[test]
+fn weighted_reactions_share_one_roll_and_duplicates_are_reported() {
+    use crate::domain::content_report::ContentWarning;
+    use crate::elements::pick_reaction;
+
+    let json = test_bundle_json(&[
+        ("base:empty", "utility"),
+        ("mod:water", "liquid"),
+        ("mod:lava", "liquid"),
+        ("mod:stone", "solid"),
+        ("mod:obsidian", "solid"),
+    ]);
+    let mut bundle: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let reaction = |victim_becomes: u16, chance: f64| {
+        serde_json::json!({
+            "aggressorId": 1, "victimId": 2, "resultAggressorId": null,
+            "resultVictimId": victim_becomes, "spawnId": null, "chance": chance
+        })
+    };
+    bundle["reactions"] = serde_json::json!([reaction(3, 0.7), reaction(4, 0.3)]);
+    let (registry, report) = ContentRegistry::from_bundle_json_with_report(&bundle.to_string());
+    assert!(report.errors.is_empty() && report.warnings.is_empty(), "{:?}", report);
+    let registry = registry.unwrap();
+
+    // 70% stone (rolls 0..179), 30% obsidian (the rest)
+    let candidates = registry.reactions(1, 2);
+    assert_eq!(candidates.iter().map(|r| r.target_becomes).collect::<Vec<_>>(), [3, 4]);
+    assert_eq!(pick_reaction(candidates, 0, 20.0).map(|r| r.target_becomes), Some(3));
+    assert_eq!(pick_reaction(candidates, 178, 20.0).map(|r| r.target_becomes), Some(3));
+    assert_eq!(pick_reaction(candidates, 179, 20.0).map(|r| r.target_becomes), Some(4));
+    assert_eq!(pick_reaction(candidates, 254, 20.0).map(|r| r.target_becomes), Some(4));
+    assert_eq!(pick_reaction(candidates, 255, 20.0).map(|r| r.target_becomes), Some(4));
+    assert!(pick_reaction(&candidates[..1], 179, 20.0).is_none());
+    assert!(registry.reactions(2, 1).is_empty());
+
+    // A repeated outcome is kept but reported, as is a pair whose chances exceed 1
+    bundle["reactions"] = serde_json::json!([reaction(3, 0.7), reaction(4, 0.3), reaction(3, 0.2)]);
+    let (registry, report) = ContentRegistry::from_bundle_json_with_report(&bundle.to_string());
+    assert_eq!(
+        report.warnings,
+        vec![
+            ContentWarning::DuplicateReaction { reaction: "mod:water + mod:lava".into() },
+            ContentWarning::ReactionChanceOverflow { reaction: "mod:water + mod:lava".into() },
+        ]
+    );
+    assert_eq!(registry.unwrap().reactions(1, 2).len(), 3);
+}
+
+#[test]
+fn reactions_respect_temperature_gate_and_release_heat() {
+    use crate::elements::{pick_reaction, Reaction};
+
+    let json = test_bundle_json(&[
+        ("base:empty", "utility"),
+        ("mod:fuel", "solid"),
+        ("mod:oxidizer", "solid"),
+        ("mod:ash", "solid"),
+    ]);
+    let mut bundle: serde_json::Value = serde_json::from_str(&json).unwrap();
+    bundle["reactions"] = serde_json::json!([{
+        "aggressorId": 1, "victimId": 2, "resultAggressorId": null, "resultVictimId": 3,
+        "spawnId": null, "chance": 1.0, "minTemp": 100.0, "heatDelta": 200.0
+    }]);
+    let mut world = WorldCore::new(4, 4);
+    world.load_content_bundle_json(&bundle.to_string()).unwrap();
+    let reaction = world.content.reactions(1, 2)[0];
+    assert_eq!((reaction.min_temp, reaction.max_temp, reaction.heat_delta), (100.0, f32::INFINITY, 200.0));
+
+    // (1, 0) is the only occupied neighbor of the corner cell
+    assert!(world.add_particle(0, 0, 1));
+    assert!(world.add_particle(1, 0, 2));
+    let react = |world: &mut WorldCore| {
+        for _ in 0..64 {
+            world.process_reactions(0, 0, 1);
+        }
+    };
+
+    world.grid.set_temp(0, 0, 20.0);
+    world.grid.set_temp(1, 0, 20.0);
+    react(&mut world);
+    assert_eq!(world.grid.get_type(1, 0), 2, "too cold to react");
+
+    // Mean of 60 and 160 reaches the gate
+    world.grid.set_temp(0, 0, 60.0);
+    world.grid.set_temp(1, 0, 160.0);
+    react(&mut world);
+    assert_eq!(world.grid.get_type(1, 0), 3);
+    assert_eq!(world.grid.get_type(0, 0), 1);
+    assert_eq!((world.grid.get_temp(0, 0), world.grid.get_temp(1, 0)), (260.0, 360.0));
+
+    // A gated-out candidate takes no slice of the roll
+    let hot = Reaction { chance: 128, ..reaction };
+    let any = Reaction { chance: 128, target_becomes: 0, min_temp: f32::NEG_INFINITY, ..reaction };
+    let candidates = [hot, any];
+    assert_eq!(pick_reaction(&candidates, 0, 20.0).map(|r| r.target_becomes), Some(0));
+    assert_eq!(pick_reaction(&candidates, 0, 150.0).map(|r| r.target_becomes), Some(3));
+    assert_eq!(pick_reaction(&candidates, 130, 150.0).map(|r| r.target_becomes), Some(0));
+
+    bundle["reactions"][0]["maxTemp"] = serde_json::json!(50.0);
+    let report = ContentRegistry::validate_bundle_json(&bundle.to_string());
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].to_string(), "reaction mod:fuel + mod:oxidizer: minTemp 100 is above maxTemp 50");
+}
+
+#[test]
+fn wildcard_reactions_expand_at_load_and_yield_to_explicit_pairs() {
+    use crate::domain::content_report::{ContentError, ContentWarning};
+
+    let json = test_bundle_json(&[
+        ("base:empty", "utility"),
+        ("mod:fire", "energy"),
+        ("mod:wood", "solid"),
+        ("mod:oil", "liquid"),
+        ("mod:moss", "bio"),
+        ("mod:stone", "solid"),
+        ("mod:ash", "powder"),
+    ]);
+    let mut bundle: serde_json::Value = serde_json::from_str(&json).unwrap();
+    bundle["elements"][2]["flags"]["flammable"] = serde_json::json!(true);
+    bundle["elements"][3]["flags"]["flammable"] = serde_json::json!(true);
+    bundle["elements"][4]["tags"] = serde_json::json!(["organic"]);
+    let burn = |victim: serde_json::Value, chance: f64| {
+        let mut r = serde_json::json!({
+            "aggressorId": 1, "resultAggressorId": null, "resultVictimId": 6, "spawnId": null, "chance": chance
+        });
+        match victim {
+            serde_json::Value::Number(id) => r["victimId"] = serde_json::Value::Number(id),
+            matcher => r["victimMatch"] = matcher,
+        }
+        r
+    };
+    bundle["reactions"] = serde_json::json!([
+        burn(serde_json::json!({ "flag": "flammable" }), 0.5),
+        burn(serde_json::json!(3), 1.0),
+        burn(serde_json::json!({ "tag": "organic" }), 0.2),
+        {
+            "aggressorMatch": { "category": "liquid" }, "victimMatch": { "category": "gas" },
+            "resultAggressorId": null, "resultVictimId": 0, "spawnId": null, "chance": 1.0
+        }
+    ]);
+    let (registry, report) = ContentRegistry::from_bundle_json_with_report(&bundle.to_string());
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
+    assert_eq!(
+        report.warnings,
+        vec![ContentWarning::WildcardMatchesNothing { reaction: "category:liquid + category:gas".into() }]
+    );
+    let registry = registry.unwrap();
+
+    let chances = |victim| registry.reactions(1, victim).iter().map(|r| r.chance).collect::<Vec<_>>();
+    assert_eq!(chances(2), [128], "flammable wood");
+    assert_eq!(chances(3), [255], "explicit oil reaction wins over the flag wildcard");
+    assert_eq!(chances(4), [51], "tagged moss");
+    assert!(chances(5).is_empty() && chances(0).is_empty());
+    assert!(registry.manifest_json().contains(r#""tags":["organic"]"#));
+
+    bundle["reactions"] = serde_json::json!([
+        burn(serde_json::json!({ "flag": "shiny" }), 0.5),
+        { "aggressorId": 1, "resultAggressorId": null, "resultVictimId": 6, "spawnId": null, "chance": 1.0 }
+    ]);
+    let report = ContentRegistry::validate_bundle_json(&bundle.to_string());
+    assert_eq!(
+        report.errors,
+        vec![
+            ContentError::UnknownMatchFlag { reaction: "mod:fire + flag:shiny".into(), flag: "shiny".into() },
+            ContentError::InvalidReactionSide { reaction: "mod:fire + ?".into(), side: "victim" },
+        ]
+    );
+
+    // Wildcards that overlap on a pair share its roll, and their summed chance is checked
+    bundle["reactions"] = serde_json::json!([
+        burn(serde_json::json!({ "flag": "flammable" }), 0.6),
+        burn(serde_json::json!({ "category": "solid" }), 0.6)
+    ]);
+    let (registry, report) = ContentRegistry::from_bundle_json_with_report(&bundle.to_string());
+    assert_eq!(
+        report.warnings,
+        vec![ContentWarning::ReactionChanceOverflow { reaction: "mod:fire + category:solid".into() }]
+    );
+    assert_eq!(registry.unwrap().reactions(1, 2).len(), 2);
+}
@@ -21,4 +21,4 @@ pub use types::PhysicsResult;
 pub use forces::{apply_friction, apply_gravity};
 pub use raycast::raycast_move;
 pub use collision::handle_collision;
-pub use update::update_particle_physics;
+pub use update::{is_velocity_driven, update_particle_physics};
@@ -1,11 +1,20 @@
 use crate::domain::content::ContentRegistry;
-use crate::elements::{EL_EMPTY, GRAVITY, AIR_FRICTION, MAX_VELOCITY, CAT_GAS, CAT_SOLID, CAT_ENERGY, CAT_BIO};
+use crate::elements::{ElementProps, EL_EMPTY, GRAVITY, AIR_FRICTION, MAX_VELOCITY, CAT_GAS, CAT_SOLID, CAT_ENERGY, CAT_BIO};
 use crate::grid::Grid;
 
 use super::collision::handle_collision;
 use super::raycast::raycast_move;
 use super::types::PhysicsResult;
 
+/// Whether the physics pass moves this element by its velocity.
+///
+/// Solids, energy and bio are pinned; gases rise via gas.rs behavior and
+/// "ignore gravity" elements are left to their behaviors. Tools that write
+/// vx/vy should skip anything this rejects.
+pub fn is_velocity_driven(props: &ElementProps) -> bool {
+    !matches!(props.category, CAT_SOLID | CAT_ENERGY | CAT_BIO | CAT_GAS) && !props.ignores_gravity()
+}
+
 /// Full physics update for a single particle
 /// Returns true if particle moved
 /// 
@@ -54,14 +63,8 @@ pub fn update_particle_physics(
         return PhysicsResult::no_move(x, y);
     }
 
-    // Respect explicit "ignore gravity" flag for future elements
-    if props.ignores_gravity() {
-        return PhysicsResult::no_move(x, y);
-    }
-
-    // Skip gases - they rise (inverted gravity) and are handled by gas.rs behavior
-    // Processing them here would cause double-processing issues
-    if props.category == CAT_GAS {
+    // Gases and "ignore gravity" elements keep their velocity but are not moved here
+    if !is_velocity_driven(props) {
         return PhysicsResult::no_move(x, y);
     }
 
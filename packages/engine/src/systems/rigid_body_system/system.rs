@@ -1,5 +1,5 @@
 use crate::domain::content::ContentRegistry;
-use crate::elements::{GRAVITY, CAT_SOLID};
+use crate::elements::{ElementId, GRAVITY, CAT_SOLID};
 use crate::grid::Grid;
 use crate::rigid_body::{RigidBody, Vec2};
 
@@ -86,6 +86,71 @@ impl RigidBodySystem {
         self.bodies.len()
     }
 
+    /// All bodies in update order (used by snapshots).
+    pub fn bodies(&self) -> &[RigidBody] {
+        &self.bodies
+    }
+
+    /// Translate pixel elements after a content swap.
+    ///
+    /// Pixels mapped to `None` are dropped; bodies left without pixels are removed.
+    pub fn remap_elements(&mut self, map: impl Fn(ElementId) -> Option<ElementId>) {
+        for body in self.bodies.iter_mut() {
+            body.pixels.retain_mut(|p| match map(p.element) {
+                Some(id) => {
+                    p.element = id;
+                    true
+                }
+                None => false,
+            });
+        }
+        self.bodies.retain(|b| !b.pixels.is_empty());
+    }
+
+    /// Shift all bodies by `(dx, dy)` after the grid was resized to `width` x `height`.
+    ///
+    /// Rasterized cells outside the new bounds are forgotten (the grid copy already
+    /// dropped them); bodies left without any cell inside are removed.
+    pub fn translate(&mut self, dx: i32, dy: i32, width: u32, height: u32) {
+        let (w, h) = (width as i32, height as i32);
+        for body in self.bodies.iter_mut() {
+            body.pos.x += dx as f32;
+            body.pos.y += dy as f32;
+            body.prev_pos.x += dx as f32;
+            body.prev_pos.y += dy as f32;
+            body.prev_world_coords.retain_mut(|(x, y)| {
+                *x += dx;
+                *y += dy;
+                *x >= 0 && *x < w && *y >= 0 && *y < h
+            });
+        }
+        self.bodies.retain(|b| !b.prev_world_coords.is_empty());
+    }
+
+    /// ID of the body rasterized at `(x, y)`, if any (linear scan, meant for queries).
+    pub fn body_at(&self, x: i32, y: i32) -> Option<u32> {
+        self.bodies
+            .iter()
+            .find(|b| b.prev_world_coords.contains(&(x, y)))
+            .map(|b| b.id)
+    }
+
+    /// ID that will be assigned to the next spawned body.
+    pub fn next_id(&self) -> u32 {
+        self.next_id
+    }
+
+    /// Rebuild a system from previously saved bodies.
+    ///
+    /// Bodies are expected to already be rasterized into the grid
+    /// (their `prev_world_coords` describe the occupied cells).
+    pub fn from_parts(bodies: Vec<RigidBody>, next_id: u32) -> Self {
+        Self {
+            bodies,
+            next_id: next_id.max(1),
+        }
+    }
+
     /// Main update loop (runs before particle physics).
     pub fn update(
         &mut self,
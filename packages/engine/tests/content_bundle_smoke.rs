@@ -21,6 +21,15 @@ fn content_bundle_smoke_parses_and_has_core_invariants() {
     // Basic key lookup should work for the base pack.
     assert_eq!(registry.id_by_key("base:empty"), Some(EL_EMPTY));
 
-    // Reaction table should be addressable (may be None depending on pair).
-    let _ = registry.reaction(EL_WATER, EL_WATER);
+    // Reaction table should be addressable (may be empty depending on pair).
+    let _ = registry.reactions(EL_WATER, EL_WATER);
+}
+
+#[test]
+fn content_bundle_smoke_has_no_validation_errors() {
+    let json = fs::read_to_string("../../apps/web/public/content/bundle.json")
+        .expect("bundle.json should exist (run content compiler first)");
+
+    let report = ContentRegistry::validate_bundle_json(&json);
+    assert!(report.errors.is_empty(), "{:?}", report.errors);
 }
@@ -66,6 +66,26 @@ function normalizeElementRef(ref, packId) {
   return `${packId}:${ref}`
 }
 
+const REACTION_MATCH_KINDS = ['category', 'flag', 'tag']
+
+// Reaction side: an element ref, or a wildcard { category } / { flag } / { tag }
+// that the engine expands to every matching element at load time
+function normalizeReactionSide(side, packId, ctx) {
+  if (typeof side === 'string') return normalizeElementRef(side, packId)
+  const keys = isRecord(side) ? Object.keys(side) : []
+  assert(
+    keys.length === 1 && REACTION_MATCH_KINDS.includes(keys[0]) && typeof side[keys[0]] === 'string',
+    `${ctx}: must be an element ref or one of { category | flag | tag }`,
+  )
+  return { [keys[0]]: side[keys[0]] }
+}
+
+function reactionSideKey(side) {
+  if (typeof side === 'string') return side
+  const [kind] = Object.keys(side)
+  return `${kind}:${side[kind]}`
+}
+
 function normalizeElementFlags(flags) {
   const f = isRecord(flags) ? flags : {}
   return {
@@ -157,6 +177,9 @@ async function loadLegacyBase() {
       resultVictim: resultVictim === null ? null : normalizeElementRef(resultVictim, packId),
       spawn: r.spawn === null ? null : normalizeElementRef(r.spawn, packId),
       chance: r.chance,
+      minTemp: r.min_temp,
+      maxTemp: r.max_temp,
+      heatDelta: r.heat_delta,
       comment: typeof r.comment === 'string' ? r.comment : undefined,
       _src: path.relative(repoRoot, legacyReactionsPath),
     }
@@ -184,6 +207,9 @@ async function loadPack(packDir) {
     assert(isRecord(el), `${file}: must be object`)
     assert(el.kind === 'element', `${file}: kind must be 'element'`) 
     assert(typeof el.key === 'string' && el.key.length > 0, `${file}: key must be string`) 
+    if (el.tags !== undefined) {
+      assert(Array.isArray(el.tags) && el.tags.every((t) => typeof t === 'string'), `${file}: tags must be an array of strings`)
+    }
 
     if (el.id !== undefined) {
       assert(Number.isInteger(el.id), `${file}: id must be integer when present`)
@@ -212,6 +238,7 @@ async function loadPack(packDir) {
       phaseChange: el.phaseChange ?? null,
       hidden: el.hidden ?? false,
       ui: el.ui ?? null,
+      tags: el.tags,
       _src: path.relative(repoRoot, file),
     })
   }
@@ -227,12 +254,15 @@ async function loadPack(packDir) {
     reactions.push({
       id: `${pack.id}:${r.id}`,
       pack: pack.id,
-      aggressor: normalizeElementRef(r.aggressor, pack.id),
-      victim: normalizeElementRef(r.victim, pack.id),
+      aggressor: normalizeReactionSide(r.aggressor, pack.id, `${file}: aggressor`),
+      victim: normalizeReactionSide(r.victim, pack.id, `${file}: victim`),
       resultAggressor: r.resultAggressor === null ? null : normalizeElementRef(r.resultAggressor, pack.id),
       resultVictim: normalizedResultVictim === null ? null : normalizeElementRef(normalizedResultVictim, pack.id),
       spawn: r.spawn === null ? null : normalizeElementRef(r.spawn, pack.id),
       chance: r.chance,
+      minTemp: r.minTemp,
+      maxTemp: r.maxTemp,
+      heatDelta: r.heatDelta,
       comment: typeof r.comment === 'string' ? r.comment : undefined,
       _src: path.relative(repoRoot, file),
     })
@@ -332,24 +362,32 @@ async function main() {
   }
 
   // Reaction merge strategy:
-  // - Reactions are keyed by (aggressor, victim)
-  // - Later packs override earlier definitions for the same pair
+  // - Reactions are grouped by (aggressor, victim)
+  // - One source (legacy definitions or a pack) may list several weighted
+  //   outcomes for a pair; they are kept in declaration order
+  // - A later pack replaces all earlier definitions for the same pair
   const reactionsByPair = new Map()
-  const allReactions = [...legacy.reactions]
-  for (const { reactions: packReactions } of loaded) {
-    for (const r of packReactions) allReactions.push(r)
-  }
+  const allReactions = legacy.reactions.map((r) => ({ r, layer: 0 }))
+  loaded.forEach(({ reactions: packReactions }, idx) => {
+    for (const r of packReactions) allReactions.push({ r, layer: idx + 1 })
+  })
 
-  for (const r of allReactions) {
-      assert(typeof r.aggressor === 'string' && typeof r.victim === 'string', `Invalid reaction refs in ${r.id}`)
-      assert(elementKeyToId[r.aggressor] !== undefined, `Unknown aggressor element: ${r.aggressor} (in ${r.id})`)
-      assert(elementKeyToId[r.victim] !== undefined, `Unknown victim element: ${r.victim} (in ${r.id})`)
+  for (const { r, layer } of allReactions) {
+      assert(r.aggressor && r.victim, `Invalid reaction refs in ${r.id}`)
+      // Wildcard sides are emitted as aggressorMatch/victimMatch instead of an id
+      for (const side of ['aggressor', 'victim']) {
+        if (typeof r[side] === 'string') {
+          assert(elementKeyToId[r[side]] !== undefined, `Unknown ${side} element: ${r[side]} (in ${r.id})`)
+        }
+      }
 
-      const pairKey = `${r.aggressor}::${r.victim}`
+      const pairKey = `${reactionSideKey(r.aggressor)}::${reactionSideKey(r.victim)}`
       const out = {
         ...r,
-        aggressorId: elementKeyToId[r.aggressor],
-        victimId: elementKeyToId[r.victim],
+        aggressorId: typeof r.aggressor === 'string' ? elementKeyToId[r.aggressor] : undefined,
+        aggressorMatch: typeof r.aggressor === 'string' ? undefined : r.aggressor,
+        victimId: typeof r.victim === 'string' ? elementKeyToId[r.victim] : undefined,
+        victimMatch: typeof r.victim === 'string' ? undefined : r.victim,
         resultAggressorId: r.resultAggressor === null ? null : elementKeyToId[r.resultAggressor],
         resultVictimId: r.resultVictim === null ? null : elementKeyToId[r.resultVictim],
         spawnId: r.spawn === null ? null : elementKeyToId[r.spawn],
@@ -367,12 +405,25 @@ async function main() {
 
       assert(typeof r.chance === 'number' && Number.isFinite(r.chance) && r.chance >= 0 && r.chance <= 1, `Invalid chance in ${r.id}`)
 
-      reactionsByPair.set(pairKey, out)
+      // Optional: temperature gate (mean of both cells) and heat released per reaction
+      for (const field of ['minTemp', 'maxTemp', 'heatDelta']) {
+        assert(r[field] === undefined || (typeof r[field] === 'number' && Number.isFinite(r[field])), `Invalid ${field} in ${r.id}`)
+      }
+      assert(r.minTemp === undefined || r.maxTemp === undefined || r.minTemp <= r.maxTemp, `minTemp above maxTemp in ${r.id}`)
+
+      const existing = reactionsByPair.get(pairKey)
+      if (existing && existing.layer === layer) {
+        existing.outcomes.push(out)
+      } else {
+        reactionsByPair.set(pairKey, { layer, outcomes: [out] })
+      }
   }
 
-  const reactions = Array.from(reactionsByPair.values()).sort((a, b) => {
-    if (a.aggressorId !== b.aggressorId) return a.aggressorId - b.aggressorId
-    return a.victimId - b.victimId
+  // Stable sort keeps each pair's outcomes in declaration order; wildcards go last
+  const sortId = (id) => id ?? Number.MAX_SAFE_INTEGER
+  const reactions = Array.from(reactionsByPair.values()).flatMap((pair) => pair.outcomes).sort((a, b) => {
+    if (sortId(a.aggressorId) !== sortId(b.aggressorId)) return sortId(a.aggressorId) - sortId(b.aggressorId)
+    return sortId(a.victimId) - sortId(b.victimId)
   })
 
   const bundle = {
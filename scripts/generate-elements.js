@@ -161,7 +161,8 @@ for (let i = 0; i < elements.length; i++) {
 // Strict reference validation for reactions
 assert(Array.isArray(reactions), `definitions/reactions.json: 'reactions' must be an array`);
 const reactionIdSet = new Set();
-const reactionPairSet = new Set();
+const reactionOutcomeSet = new Set();
+const reactionPairChance = new Map();
 for (let i = 0; i < reactions.length; i++) {
   const r = reactions[i];
   assert(typeof r.id === 'string' && r.id.length > 0, `definitions/reactions.json: reactions[${i}].id must be a string`);
@@ -171,9 +172,11 @@ for (let i = 0; i < reactions.length; i++) {
   assert(elementNameSet.has(r.aggressor), `definitions/reactions.json: reactions[${i}] '${r.id}' aggressor '${r.aggressor}' not found in elements`);
   assert(elementNameSet.has(r.victim), `definitions/reactions.json: reactions[${i}] '${r.id}' victim '${r.victim}' not found in elements`);
 
+  // Several reactions per pair are weighted outcomes of one roll
   const pairKey = `${r.aggressor}::${r.victim}`;
-  assert(!reactionPairSet.has(pairKey), `definitions/reactions.json: duplicate reaction pair ${pairKey}`);
-  reactionPairSet.add(pairKey);
+  const outcomeKey = `${pairKey}->${r.result_aggressor}/${r.result_victim}/${r.spawn}`;
+  assert(!reactionOutcomeSet.has(outcomeKey), `definitions/reactions.json: duplicate reaction outcome for pair ${pairKey} ('${r.id}')`);
+  reactionOutcomeSet.add(outcomeKey);
 
   if (r.result_aggressor !== null) {
     assert(elementNameSet.has(r.result_aggressor), `definitions/reactions.json: reactions[${i}] '${r.id}' result_aggressor '${r.result_aggressor}' not found in elements`);
@@ -187,6 +190,19 @@ for (let i = 0; i < reactions.length; i++) {
 
   assert(typeof r.chance === 'number' && Number.isFinite(r.chance), `definitions/reactions.json: reactions[${i}] '${r.id}' chance must be a number`);
   assert(r.chance >= 0 && r.chance <= 1, `definitions/reactions.json: reactions[${i}] '${r.id}' chance must be in [0..1]`);
+
+  for (const field of ['min_temp', 'max_temp', 'heat_delta']) {
+    if (r[field] !== undefined) {
+      assert(typeof r[field] === 'number' && Number.isFinite(r[field]), `definitions/reactions.json: reactions[${i}] '${r.id}' ${field} must be a number`);
+    }
+  }
+  if (r.min_temp !== undefined && r.max_temp !== undefined) {
+    assert(r.min_temp <= r.max_temp, `definitions/reactions.json: reactions[${i}] '${r.id}' min_temp must not exceed max_temp`);
+  }
+
+  const pairChance = (reactionPairChance.get(pairKey) ?? 0) + r.chance;
+  assert(pairChance <= 1 + 1e-9, `definitions/reactions.json: chances for pair ${pairKey} add up to more than 1`);
+  reactionPairChance.set(pairKey, pairChance);
 }
 
 // Build name->id map for reactions
@@ -252,13 +268,6 @@ function computeFlags(el) {
   return flags;
 }
 
-/**
- * Build reaction LUT index: (aggressor_id << 8) | victim_id
- */
-function reactionIndex(aggId, vicId) {
-  return (aggId << 8) | vicId;
-}
-
 // ============================================================================
 // RUST CODE GENERATION
 // ============================================================================
@@ -292,8 +301,11 @@ function generateRust() {
   lines.push(``);
   lines.push(`use wasm_bindgen::prelude::*;`);
   lines.push(``);
-  lines.push(`/// Element ID as u8 for compact storage`);
+  lines.push(`/// Element ID as u8 for compact storage (u16 with the \`wide-element-ids\` feature)`);
+  lines.push(`#[cfg(not(feature = "wide-element-ids"))]`);
   lines.push(`pub type ElementId = u8;`);
+  lines.push(`#[cfg(feature = "wide-element-ids")]`);
+  lines.push(`pub type ElementId = u16;`);
   lines.push(``);
   
   // Element constants
@@ -509,34 +521,43 @@ function generateRust() {
   lines.push(`}`);
   lines.push(``);
   
-  // Reaction LUT
+  // Reaction table
   lines.push(`// ============================================================================`);
-  lines.push(`// REACTION LOOKUP TABLE (LUT) - O(1) Access`);
+  lines.push(`// REACTION TABLE - sparse, per aggressor`);
   lines.push(`// ============================================================================`);
   lines.push(``);
-  lines.push(`/// Reaction result from LUT`);
+  lines.push(`/// Reaction result`);
   lines.push(`#[derive(Clone, Copy, Debug)]`);
   lines.push(`pub struct Reaction {`);
   lines.push(`    /// What victim becomes (EL_EMPTY = destroyed)`);
   lines.push(`    pub target_becomes: ElementId,`);
-  lines.push(`    /// What aggressor becomes (255 = unchanged, EL_EMPTY = destroyed)`);
-  lines.push(`    pub source_becomes: u8,`);
+  lines.push(`    /// What aggressor becomes (NO_CHANGE = unchanged, EL_EMPTY = destroyed)`);
+  lines.push(`    pub source_becomes: ElementId,`);
   lines.push(`    /// Probability 0-255 (255 = 100%)`);
   lines.push(`    pub chance: u8,`);
   lines.push(`    /// Spawn byproduct (EL_EMPTY = none)`);
   lines.push(`    pub spawn: ElementId,`);
+  lines.push(`    /// Fires only when the mean temperature of both cells is in`);
+  lines.push(`    /// \`min_temp..=max_temp\` (infinite = no limit). The mean keeps the gate`);
+  lines.push(`    /// symmetric: a hot aggressor next to a cold victim counts the same as the reverse.`);
+  lines.push(`    pub min_temp: f32,`);
+  lines.push(`    pub max_temp: f32,`);
+  lines.push(`    /// Degrees added to both cells when it fires (exothermic > 0, endothermic < 0)`);
+  lines.push(`    pub heat_delta: f32,`);
   lines.push(`}`);
   lines.push(``);
   lines.push(`impl Reaction {`);
-  lines.push(`    pub const NO_CHANGE: u8 = 255;`);
+  lines.push(`    /// Reserved id: never a valid element`);
+  lines.push(`    pub const NO_CHANGE: ElementId = ElementId::MAX;`);
+  lines.push(`    `);
+  lines.push(`    /// Temperature gate check`);
+  lines.push(`    #[inline]`);
+  lines.push(`    pub fn accepts_temp(&self, temp: f32) -> bool {`);
+  lines.push(`        temp >= self.min_temp && temp <= self.max_temp`);
+  lines.push(`    }`);
   lines.push(`}`);
   lines.push(``);
   
-  // Generate LUT entries
-  lines.push(`/// Reaction LUT size: 256 * 256 = 65536 entries`);
-  lines.push(`pub const REACTION_LUT_SIZE: usize = 65536;`);
-  lines.push(``);
-  
   // Build sparse reaction data
   const reactionEntries = [];
   for (const r of reactions) {
@@ -547,60 +568,100 @@ function generateRust() {
       continue;
     }
     
-    const resultAgg = r.result_aggressor === null ? 255 : 
-                      (nameToId[r.result_aggressor] ?? 255);
+    const resultAgg = r.result_aggressor === null ? 'Reaction::NO_CHANGE' :
+                      (nameToId[r.result_aggressor] ?? 'Reaction::NO_CHANGE');
     const resultVic = r.result_victim === null ? 0 :
                       (nameToId[r.result_victim] ?? 0);
     const spawn = r.spawn === null ? 0 : (nameToId[r.spawn] ?? 0);
     const chance = Math.round(r.chance * 255);
-    const idx = reactionIndex(aggId, vicId);
+    const minTemp = r.min_temp === undefined ? 'f32::NEG_INFINITY' : toRustFloat(r.min_temp);
+    const maxTemp = r.max_temp === undefined ? 'f32::INFINITY' : toRustFloat(r.max_temp);
+    const heatDelta = toRustFloat(r.heat_delta ?? 0);
     
-    reactionEntries.push({ idx, resultAgg, resultVic, chance, spawn, id: r.id });
+    reactionEntries.push({ aggId, vicId, resultAgg, resultVic, chance, spawn, minTemp, maxTemp, heatDelta, id: r.id });
   }
   
-  // Sort by index for predictable output
-  reactionEntries.sort((a, b) => a.idx - b.idx);
+  // Sort by (aggressor, victim) for predictable output; stable, so candidates
+  // for one pair keep their declaration order
+  reactionEntries.sort((a, b) => a.aggId - b.aggId || a.vicId - b.vicId);
   
-  lines.push(`/// Reaction LUT initialization data (sparse): (index, reaction)`);
-  lines.push(`pub static REACTION_INIT_DATA: [(usize, Reaction); ${reactionEntries.length}] = [`);
+  lines.push(`/// Built-in reactions (sparse): (aggressor, victim, reaction)`);
+  lines.push(`pub static REACTION_INIT_DATA: [(ElementId, ElementId, Reaction); ${reactionEntries.length}] = [`);
   for (const r of reactionEntries) {
-    lines.push(`    (${r.idx}, Reaction { target_becomes: ${r.resultVic}, source_becomes: ${r.resultAgg}, chance: ${r.chance}, spawn: ${r.spawn} }), // ${r.id}`);
+    lines.push(`    (${r.aggId}, ${r.vicId}, Reaction { target_becomes: ${r.resultVic}, source_becomes: ${r.resultAgg}, chance: ${r.chance}, spawn: ${r.spawn}, min_temp: ${r.minTemp}, max_temp: ${r.maxTemp}, heat_delta: ${r.heatDelta} }), // ${r.id}`);
   }
   lines.push(`];`);
   lines.push(``);
   
   // ReactionSystem struct
-  lines.push(`/// Reaction System with O(1) lookup`);
+  lines.push(`/// Sparse reaction table: one list per aggressor, sorted by victim.`);
+  lines.push(`/// Memory grows with the number of reactions, not with element count squared.`);
+  lines.push(`/// A pair may have several candidate reactions; one roll picks at most one.`);
+  lines.push(`#[derive(Clone, Debug, Default)]`);
   lines.push(`pub struct ReactionSystem {`);
-  lines.push(`    lut: [Option<Reaction>; REACTION_LUT_SIZE],`);
+  lines.push(`    by_aggressor: Vec<Vec<(ElementId, Vec<Reaction>)>>,`);
   lines.push(`}`);
   lines.push(``);
   lines.push(`impl ReactionSystem {`);
   lines.push(`    /// Create new reaction system from init data`);
   lines.push(`    pub fn new() -> Self {`);
-  lines.push(`        // Initialize with None`);
-  lines.push(`        let mut lut = [None; REACTION_LUT_SIZE];`);
-  lines.push(`        `);
-  lines.push(`        // Populate from generated data`);
-  lines.push(`        for (idx, reaction) in REACTION_INIT_DATA.iter() {`);
-  lines.push(`            lut[*idx] = Some(*reaction);`);
+  lines.push(`        let mut system = Self::default();`);
+  lines.push(`        for (aggressor, victim, reaction) in REACTION_INIT_DATA.iter() {`);
+  lines.push(`            system.add(*aggressor, *victim, *reaction);`);
   lines.push(`        }`);
-  lines.push(`        `);
-  lines.push(`        Self { lut }`);
+  lines.push(`        system`);
   lines.push(`    }`);
   lines.push(`    `);
-  lines.push(`    /// O(1) reaction lookup - FAST!`);
+  lines.push(`    /// Append a candidate reaction for a pair (after any existing ones)`);
+  lines.push(`    pub fn add(&mut self, aggressor: ElementId, victim: ElementId, reaction: Reaction) {`);
+  lines.push(`        let idx = aggressor as usize;`);
+  lines.push(`        if idx >= self.by_aggressor.len() {`);
+  lines.push(`            self.by_aggressor.resize_with(idx + 1, Vec::new);`);
+  lines.push(`        }`);
+  lines.push(`        let list = &mut self.by_aggressor[idx];`);
+  lines.push(`        match list.binary_search_by_key(&victim, |(v, _)| *v) {`);
+  lines.push(`            Ok(i) => list[i].1.push(reaction),`);
+  lines.push(`            Err(i) => list.insert(i, (victim, vec![reaction])),`);
+  lines.push(`        }`);
+  lines.push(`    }`);
+  lines.push(`    `);
+  lines.push(`    /// Candidate reactions for a pair, in declaration order (binary search in the aggressor's list)`);
   lines.push(`    #[inline(always)]`);
-  lines.push(`    pub fn get(&self, aggressor: ElementId, victim: ElementId) -> Option<&Reaction> {`);
-  lines.push(`        let idx = ((aggressor as usize) << 8) | (victim as usize);`);
-  lines.push(`        // SAFETY: idx is always < 65536 since both IDs are u8`);
-  lines.push(`        #[cfg(not(debug_assertions))]`);
-  lines.push(`        unsafe { self.lut.get_unchecked(idx).as_ref() }`);
-  lines.push(`        #[cfg(debug_assertions)]`);
-  lines.push(`        self.lut[idx].as_ref()`);
+  lines.push(`    pub fn get(&self, aggressor: ElementId, victim: ElementId) -> &[Reaction] {`);
+  lines.push(`        let Some(list) = self.by_aggressor.get(aggressor as usize) else {`);
+  lines.push(`            return &[];`);
+  lines.push(`        };`);
+  lines.push(`        match list.binary_search_by_key(&victim, |(v, _)| *v) {`);
+  lines.push(`            Ok(i) => &list[i].1,`);
+  lines.push(`            Err(_) => &[],`);
+  lines.push(`        }`);
+  lines.push(`    }`);
+  lines.push(`    `);
+  lines.push(`    /// Number of reactions (candidates of every pair)`);
+  lines.push(`    pub fn len(&self) -> usize {`);
+  lines.push(`        self.by_aggressor.iter().flatten().map(|(_, candidates)| candidates.len()).sum()`);
+  lines.push(`    }`);
+  lines.push(`    `);
+  lines.push(`    pub fn is_empty(&self) -> bool {`);
+  lines.push(`        self.len() == 0`);
   lines.push(`    }`);
   lines.push(`}`);
   lines.push(``);
+  lines.push(`/// Pick the outcome of one roll (0-255): candidates whose gate accepts \`temp\``);
+  lines.push(`/// own consecutive slices of the roll range, sized by their chance. With a`);
+  lines.push(`/// single candidate this is \`roll < chance\`.`);
+  lines.push(`#[inline]`);
+  lines.push(`pub fn pick_reaction(candidates: &[Reaction], roll: u8, temp: f32) -> Option<&Reaction> {`);
+  lines.push(`    let mut threshold = 0u32;`);
+  lines.push(`    for reaction in candidates.iter().filter(|r| r.accepts_temp(temp)) {`);
+  lines.push(`        threshold += reaction.chance as u32;`);
+  lines.push(`        if (roll as u32) < threshold {`);
+  lines.push(`            return Some(reaction);`);
+  lines.push(`        }`);
+  lines.push(`    }`);
+  lines.push(`    None`);
+  lines.push(`}`);
+  lines.push(``);
   
   // Helper functions
   lines.push(`/// Get element properties by ID`);
@@ -848,6 +909,9 @@ function generateTypeScript() {
   lines.push(`  resultVictim: ElementType | null`);
   lines.push(`  chance: number`);
   lines.push(`  spawn: ElementType | null`);
+  lines.push(`  minTemp?: number`);
+  lines.push(`  maxTemp?: number`);
+  lines.push(`  heatDelta?: number`);
   lines.push(`}`);
   lines.push(``);
   lines.push(`export const REACTIONS: Reaction[] = [`);
@@ -855,7 +919,11 @@ function generateTypeScript() {
     const resAgg = r.result_aggressor ? `'${r.result_aggressor}'` : 'null';
     const resVic = r.result_victim ? `'${r.result_victim}'` : 'null';
     const spawn = r.spawn ? `'${r.spawn}'` : 'null';
-    lines.push(`  { aggressor: '${r.aggressor}', victim: '${r.victim}', resultAggressor: ${resAgg}, resultVictim: ${resVic}, chance: ${r.chance}, spawn: ${spawn} },`);
+    const temps = [['minTemp', r.min_temp], ['maxTemp', r.max_temp], ['heatDelta', r.heat_delta]]
+      .filter(([, v]) => v !== undefined)
+      .map(([k, v]) => `, ${k}: ${v}`)
+      .join('');
+    lines.push(`  { aggressor: '${r.aggressor}', victim: '${r.victim}', resultAggressor: ${resAgg}, resultVictim: ${resVic}, chance: ${r.chance}, spawn: ${spawn}${temps} },`);
   }
   lines.push(`]`);
   lines.push(``);